@@ -12,7 +12,7 @@ fn main() {
 
     let function_sig = "createBook(u32,string)";
 
-    let params = vec![Value::U32(60), Value::String("olavm".to_string())];
+    let params: Vec<Value> = vec![60u32.into(), "olavm".into()];
 
     let input = abi
         .encode_input_with_signature(function_sig, &params)
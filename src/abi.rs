@@ -1,7 +1,13 @@
-use anyhow::{anyhow, Result};
 use serde::{de::Visitor, Deserialize, Serialize};
+use std::collections::HashMap;
 
-use crate::{params::Param, DecodedParams, Event, FixedArray4, Value};
+use crate::{
+    error::{AbiError, Result},
+    human_readable::parse_signature,
+    params::Param,
+    values::DecodeOptions,
+    DecodedParams, Error, Event, FixedArray4, Value,
+};
 
 /// Contract ABI (Abstract Binary Interface).
 ///
@@ -24,6 +30,18 @@ pub struct Abi {
     pub functions: Vec<Function>,
 
     pub events: Vec<Event>,
+
+    /// Contract defined custom errors.
+    pub errors: Vec<Error>,
+
+    /// Contract constructor.
+    pub constructor: Option<Constructor>,
+
+    /// Whether the contract has a fallback function.
+    pub fallback: bool,
+
+    /// Whether the contract has a receive function.
+    pub receive: bool,
 }
 
 impl Abi {
@@ -32,36 +50,64 @@ impl Abi {
         &'a self,
         input: &[u64],
     ) -> Result<(&'a Function, DecodedParams)> {
-        let f = self
-            .functions
-            .iter()
-            .find(|f| f.method_id() == input[input.len() - 1])
-            .ok_or_else(|| anyhow!("ABI function not found"))?;
-
-        // input = [param1, param2, .. , param-len, method_id]
-
-        let decoded_params = f.decode_input_from_slice(&input[0..input.len() - 2])?;
+        self.decode_input_from_slice_with_options(input, &DecodeOptions::default())
+    }
 
-        Ok((f, decoded_params))
+    /// Decode function input from slice using the given decoding options.
+    ///
+    /// In strict mode, the length word must match the number of words decoded.
+    pub fn decode_input_from_slice_with_options<'a>(
+        &'a self,
+        input: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        Lookup::decode_input(&self, input, opts)
     }
 
-    // Decode function ouput from slice.
+    /// Decode function output from slice, looking the function up by signature.
+    ///
+    /// The output must end with the length word, which is checked against the
+    /// decoded values.
     pub fn decode_output_from_slice<'a>(
         &'a self,
         signature: &str,
         output: &[u64],
     ) -> Result<(&'a Function, DecodedParams)> {
-        let f = self
-            .functions
-            .iter()
-            .find(|f| f.signature() == signature)
-            .ok_or_else(|| anyhow!("ABI function not found"))?;
+        self.decode_output_from_slice_with_options(signature, output, &DecodeOptions::default())
+    }
 
-        // output = [param1, param2, .. , param-len]
+    /// Decode function output from slice, looking the function up by signature,
+    /// using the given decoding options.
+    pub fn decode_output_from_slice_with_options<'a>(
+        &'a self,
+        signature: &str,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        Lookup::decode_output(&self, signature, output, opts)
+    }
 
-        let decoded_params = f.decode_output_from_slice(&output[0..output.len() - 1])?;
+    /// Decode function output from slice, looking the function up by method id.
+    ///
+    /// The output must end with the length word, which is checked against the
+    /// decoded values.
+    pub fn decode_output_by_method_id<'a>(
+        &'a self,
+        method_id: u64,
+        output: &[u64],
+    ) -> Result<(&'a Function, DecodedParams)> {
+        self.decode_output_by_method_id_with_options(method_id, output, &DecodeOptions::default())
+    }
 
-        Ok((f, decoded_params))
+    /// Decode function output from slice, looking the function up by method id,
+    /// using the given decoding options.
+    pub fn decode_output_by_method_id_with_options<'a>(
+        &'a self,
+        method_id: u64,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        Lookup::decode_output_by_method_id(&self, method_id, output, opts)
     }
 
     /// Decode event data from slice.
@@ -70,45 +116,455 @@ impl Abi {
         topics: &[FixedArray4],
         data: &[u64],
     ) -> Result<(&'a Event, DecodedParams)> {
-        if topics.is_empty() {
-            return Err(anyhow!("missing event topic id"));
+        self.decode_log_from_slice_with_options(topics, data, &DecodeOptions::default())
+    }
+
+    /// Decode event data from slice using the given decoding options.
+    pub fn decode_log_from_slice_with_options<'a>(
+        &'a self,
+        topics: &[FixedArray4],
+        data: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Event, DecodedParams)> {
+        Lookup::decode_log(&self, topics, data, opts)
+    }
+
+    /// Decode a custom error from revert data.
+    pub fn decode_error_from_slice<'a>(
+        &'a self,
+        data: &[u64],
+    ) -> Result<(&'a Error, DecodedParams)> {
+        self.decode_error_from_slice_with_options(data, &DecodeOptions::default())
+    }
+
+    /// Decode a custom error from revert data using the given decoding options.
+    ///
+    /// In strict mode, the length word must match the number of words decoded.
+    pub fn decode_error_from_slice_with_options<'a>(
+        &'a self,
+        data: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Error, DecodedParams)> {
+        Lookup::decode_error(&self, data, opts)
+    }
+
+    pub fn encode_input_with_signature(
+        &self,
+        signature: &str,
+        params: &[Value],
+    ) -> Result<Vec<u64>> {
+        Lookup::function_by_signature(&self, signature)?.encode_input(params)
+    }
+
+    pub fn encode_values(&self, params: &[Value]) -> Result<Vec<u64>> {
+        let mut params = Value::encode(params)?;
+        params.push(params.len() as u64);
+
+        Ok(params)
+    }
+
+    /// Returns all the functions with the given name, i.e. all its overloads.
+    pub fn functions_by_name<'a>(&'a self, name: &str) -> Vec<&'a Function> {
+        self.functions.iter().filter(|f| f.name == name).collect()
+    }
+
+    /// Returns the function with the given name.
+    ///
+    /// If `name` contains a parameter list (e.g. `vote(u32)`) the function is looked up
+    /// by signature instead, which is how a specific overload is selected.
+    pub fn function(&self, name: &str) -> Result<&Function> {
+        if name.contains('(') {
+            return Lookup::function_by_signature(&self, name);
         }
 
-        let e = self
-            .events
-            .iter()
-            .find(|e| e.topic() == topics[0])
-            .ok_or_else(|| anyhow!("ABI event not found"))?;
+        match self.functions_by_name(name).as_slice() {
+            [] => Err(AbiError::UnknownFunction(name.to_string())),
+            [f] => Ok(f),
+            overloads => Err(AbiError::AmbiguousFunction {
+                name: name.to_string(),
+                signatures: overloads.iter().map(|f| f.signature()).collect(),
+            }),
+        }
+    }
+
+    /// Checks that no two functions or errors share a selector and no two events
+    /// share a topic, as such entries cannot be told apart when decoding.
+    pub fn validate(&self) -> Result<()> {
+        let mut method_ids = HashMap::new();
+        for f in &self.functions {
+            if let Some(other) = method_ids.insert(f.method_id(), f) {
+                return Err(AbiError::SelectorCollision {
+                    selector: f.method_id(),
+                    first: other.signature(),
+                    second: f.signature(),
+                });
+            }
+        }
 
-        let decoded_params = e.decode_data_from_slice(topics, data)?;
+        let mut selectors = HashMap::new();
+        for e in &self.errors {
+            if let Some(other) = selectors.insert(e.selector(), e) {
+                return Err(AbiError::SelectorCollision {
+                    selector: e.selector(),
+                    first: other.signature(),
+                    second: e.signature(),
+                });
+            }
+        }
 
-        Ok((e, decoded_params))
+        let mut topics = HashMap::new();
+        for e in self.events.iter().filter(|e| !e.anonymous) {
+            if let Some(other) = topics.insert(e.topic(), e) {
+                return Err(AbiError::TopicCollision {
+                    topic: e.topic(),
+                    first: other.signature(),
+                    second: e.signature(),
+                });
+            }
+        }
+
+        Ok(())
     }
 
+    /// Builds an index for constant-time lookup of functions, events and errors.
+    ///
+    /// Method ids, selectors and topics are computed once, at index creation.
+    pub fn index(&self) -> AbiIndex<'_> {
+        AbiIndex::new(self)
+    }
+}
+
+/// Provides fast access to ABI entries by method id, signature and topic.
+pub struct AbiIndex<'a> {
+    /// Functions by method id.
+    pub functions_by_method_id: HashMap<u64, &'a Function>,
+    /// Functions by signature.
+    pub functions_by_signature: HashMap<String, &'a Function>,
+    /// Events by topic.
+    pub events_by_topic: HashMap<FixedArray4, &'a Event>,
+    /// Errors by selector.
+    pub errors_by_selector: HashMap<u64, &'a Error>,
+}
+
+impl<'a> AbiIndex<'a> {
+    fn new(abi: &'a Abi) -> Self {
+        // entries are indexed in declaration order and the first one wins, as
+        // with the lookups of `Abi`.
+        let mut functions_by_method_id = HashMap::new();
+        let mut functions_by_signature = HashMap::new();
+        for f in &abi.functions {
+            functions_by_method_id.entry(f.method_id()).or_insert(f);
+            functions_by_signature.entry(f.signature()).or_insert(f);
+        }
+
+        let mut events_by_topic = HashMap::new();
+        for e in &abi.events {
+            events_by_topic.entry(e.topic()).or_insert(e);
+        }
+
+        let mut errors_by_selector = HashMap::new();
+        for e in &abi.errors {
+            errors_by_selector.entry(e.selector()).or_insert(e);
+        }
+
+        AbiIndex {
+            functions_by_method_id,
+            functions_by_signature,
+            events_by_topic,
+            errors_by_selector,
+        }
+    }
+
+    /// Returns the function with the given method id.
+    pub fn function_by_method_id(&self, method_id: u64) -> Result<&'a Function> {
+        self.functions_by_method_id
+            .get(&method_id)
+            .copied()
+            .ok_or(AbiError::UnknownSelector(method_id))
+    }
+
+    /// Returns the function with the given signature.
+    pub fn function_by_signature(&self, signature: &str) -> Result<&'a Function> {
+        self.functions_by_signature
+            .get(signature)
+            .copied()
+            .ok_or_else(|| AbiError::UnknownSignature(signature.to_string()))
+    }
+
+    /// Returns the event with the given topic.
+    pub fn event_by_topic(&self, topic: &FixedArray4) -> Result<&'a Event> {
+        self.events_by_topic
+            .get(topic)
+            .copied()
+            .ok_or(AbiError::UnknownTopic(*topic))
+    }
+
+    /// Returns the error with the given selector.
+    pub fn error_by_selector(&self, selector: u64) -> Result<&'a Error> {
+        self.errors_by_selector
+            .get(&selector)
+            .copied()
+            .ok_or(AbiError::UnknownSelector(selector))
+    }
+
+    /// Decode function input from slice.
+    pub fn decode_input_from_slice(&self, input: &[u64]) -> Result<(&'a Function, DecodedParams)> {
+        self.decode_input_from_slice_with_options(input, &DecodeOptions::default())
+    }
+
+    /// Decode function input from slice using the given decoding options.
+    ///
+    /// In strict mode, the length word must match the number of words decoded.
+    pub fn decode_input_from_slice_with_options(
+        &self,
+        input: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        Lookup::decode_input(self, input, opts)
+    }
+
+    /// Decode function output from slice.
+    pub fn decode_output_from_slice(
+        &self,
+        signature: &str,
+        output: &[u64],
+    ) -> Result<(&'a Function, DecodedParams)> {
+        self.decode_output_from_slice_with_options(signature, output, &DecodeOptions::default())
+    }
+
+    /// Decode function output from slice using the given decoding options.
+    pub fn decode_output_from_slice_with_options(
+        &self,
+        signature: &str,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        Lookup::decode_output(self, signature, output, opts)
+    }
+
+    /// Decode function output from slice, looking the function up by method id.
+    pub fn decode_output_by_method_id(
+        &self,
+        method_id: u64,
+        output: &[u64],
+    ) -> Result<(&'a Function, DecodedParams)> {
+        self.decode_output_by_method_id_with_options(method_id, output, &DecodeOptions::default())
+    }
+
+    /// Decode function output from slice, looking the function up by method id,
+    /// using the given decoding options.
+    pub fn decode_output_by_method_id_with_options(
+        &self,
+        method_id: u64,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        Lookup::decode_output_by_method_id(self, method_id, output, opts)
+    }
+
+    /// Decode event data from slice.
+    pub fn decode_log_from_slice(
+        &self,
+        topics: &[FixedArray4],
+        data: &[u64],
+    ) -> Result<(&'a Event, DecodedParams)> {
+        self.decode_log_from_slice_with_options(topics, data, &DecodeOptions::default())
+    }
+
+    /// Decode event data from slice using the given decoding options.
+    pub fn decode_log_from_slice_with_options(
+        &self,
+        topics: &[FixedArray4],
+        data: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Event, DecodedParams)> {
+        Lookup::decode_log(self, topics, data, opts)
+    }
+
+    /// Decode a custom error from revert data.
+    pub fn decode_error_from_slice(&self, data: &[u64]) -> Result<(&'a Error, DecodedParams)> {
+        self.decode_error_from_slice_with_options(data, &DecodeOptions::default())
+    }
+
+    /// Decode a custom error from revert data using the given decoding options.
+    ///
+    /// In strict mode, the length word must match the number of words decoded.
+    pub fn decode_error_from_slice_with_options(
+        &self,
+        data: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Error, DecodedParams)> {
+        Lookup::decode_error(self, data, opts)
+    }
+
+    /// Encode function input, looking the function up by signature.
     pub fn encode_input_with_signature(
         &self,
         signature: &str,
         params: &[Value],
     ) -> Result<Vec<u64>> {
-        let f = self
-            .functions
+        self.function_by_signature(signature)?.encode_input(params)
+    }
+}
+
+/// Looks ABI entries up and decodes with them, so that `Abi`, which scans its
+/// entries, and `AbiIndex`, which hashes them once, share one decode path.
+trait Lookup<'a> {
+    fn function_by_method_id(&self, method_id: u64) -> Result<&'a Function>;
+
+    fn function_by_signature(&self, signature: &str) -> Result<&'a Function>;
+
+    fn event_by_topic(&self, topic: &FixedArray4) -> Result<&'a Event>;
+
+    fn error_by_selector(&self, selector: u64) -> Result<&'a Error>;
+
+    fn decode_input(
+        &self,
+        input: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        let (method_id, declared, params) = split_selector(input)?;
+
+        let f = self.function_by_method_id(method_id)?;
+
+        let (decoded_params, decoded) = decode_params(&f.inputs, params, opts)?;
+        if opts.strict {
+            check_length_word(declared, decoded, params.len())?;
+        }
+
+        Ok((f, decoded_params))
+    }
+
+    fn decode_output(
+        &self,
+        signature: &str,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        let f = self.function_by_signature(signature)?;
+
+        Ok((f, f.decode_output_with_len_with_options(output, opts)?))
+    }
+
+    fn decode_output_by_method_id(
+        &self,
+        method_id: u64,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Function, DecodedParams)> {
+        let f = self.function_by_method_id(method_id)?;
+
+        Ok((f, f.decode_output_with_len_with_options(output, opts)?))
+    }
+
+    fn decode_log(
+        &self,
+        topics: &[FixedArray4],
+        data: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Event, DecodedParams)> {
+        let topic = topics.first().ok_or(AbiError::MissingTopic)?;
+
+        let e = self.event_by_topic(topic)?;
+
+        Ok((
+            e,
+            e.decode_data_from_slice_with_options(topics, data, opts)?,
+        ))
+    }
+
+    fn decode_error(
+        &self,
+        data: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<(&'a Error, DecodedParams)> {
+        let (selector, declared, params) = split_selector(data)?;
+
+        let e = self.error_by_selector(selector)?;
+
+        let (decoded_params, decoded) = decode_params(&e.inputs, params, opts)?;
+        if opts.strict {
+            check_length_word(declared, decoded, params.len())?;
+        }
+
+        Ok((e, decoded_params))
+    }
+}
+
+impl<'a> Lookup<'a> for &'a Abi {
+    fn function_by_method_id(&self, method_id: u64) -> Result<&'a Function> {
+        self.functions
+            .iter()
+            .find(|f| f.method_id() == method_id)
+            .ok_or(AbiError::UnknownSelector(method_id))
+    }
+
+    fn function_by_signature(&self, signature: &str) -> Result<&'a Function> {
+        self.functions
             .iter()
             .find(|f| f.signature() == signature)
-            .ok_or_else(|| anyhow!("ABI function not found"))?;
+            .ok_or_else(|| AbiError::UnknownSignature(signature.to_string()))
+    }
 
-        let mut params = Value::encode(params);
-        params.push(params.len() as u64);
-        params.push(f.method_id());
+    fn event_by_topic(&self, topic: &FixedArray4) -> Result<&'a Event> {
+        self.events
+            .iter()
+            .find(|e| e.topic() == *topic)
+            .ok_or(AbiError::UnknownTopic(*topic))
+    }
 
-        Ok(params)
+    fn error_by_selector(&self, selector: u64) -> Result<&'a Error> {
+        self.errors
+            .iter()
+            .find(|e| e.selector() == selector)
+            .ok_or(AbiError::UnknownSelector(selector))
     }
+}
 
-    pub fn encode_values(&self, params: &[Value]) -> Result<Vec<u64>> {
-        let mut params = Value::encode(params);
-        params.push(params.len() as u64);
+impl<'a> Lookup<'a> for AbiIndex<'a> {
+    fn function_by_method_id(&self, method_id: u64) -> Result<&'a Function> {
+        AbiIndex::function_by_method_id(self, method_id)
+    }
 
-        Ok(params)
+    fn function_by_signature(&self, signature: &str) -> Result<&'a Function> {
+        AbiIndex::function_by_signature(self, signature)
+    }
+
+    fn event_by_topic(&self, topic: &FixedArray4) -> Result<&'a Event> {
+        AbiIndex::event_by_topic(self, topic)
+    }
+
+    fn error_by_selector(&self, selector: u64) -> Result<&'a Error> {
+        AbiIndex::error_by_selector(self, selector)
+    }
+}
+
+/// Splits `[param1, param2, .. , param-len, selector]` into the selector, the
+/// length word and the params.
+fn split_selector(input: &[u64]) -> Result<(u64, u64, &[u64])> {
+    if input.len() < 2 {
+        return Err(AbiError::MissingSelector);
     }
+
+    Ok((
+        input[input.len() - 1],
+        input[input.len() - 2],
+        &input[0..input.len() - 2],
+    ))
+}
+
+/// Checks that the params consumed exactly the `available` words before the
+/// length word, and that the length word declares as many.
+fn check_length_word(declared: u64, decoded: usize, available: usize) -> Result<()> {
+    if declared != decoded as u64 || decoded != available {
+        return Err(AbiError::LengthWordMismatch {
+            declared,
+            decoded,
+            available,
+        });
+    }
+
+    Ok(())
 }
 
 impl Serialize for Abi {
@@ -125,6 +581,9 @@ impl Serialize for Abi {
                 inputs: Some(f.inputs.clone()),
                 outputs: Some(f.outputs.clone()),
                 anonymous: None,
+                state_mutability: Some(f.state_mutability),
+                constant: f.constant,
+                payable: f.payable,
             });
         }
 
@@ -135,8 +594,64 @@ impl Serialize for Abi {
                 inputs: Some(e.inputs.clone()),
                 outputs: None,
                 anonymous: Some(e.anonymous),
+                state_mutability: None,
+                constant: None,
+                payable: None,
+            });
+        }
+
+        for e in &self.errors {
+            entries.push(AbiEntry {
+                type_: String::from("error"),
+                name: Some(e.name.clone()),
+                inputs: Some(e.inputs.clone()),
+                outputs: None,
+                anonymous: None,
+                state_mutability: None,
+                constant: None,
+                payable: None,
+            });
+        }
+
+        if let Some(c) = &self.constructor {
+            entries.push(AbiEntry {
+                type_: String::from("constructor"),
+                name: None,
+                inputs: Some(c.inputs.clone()),
+                outputs: None,
+                anonymous: None,
+                state_mutability: None,
+                constant: None,
+                payable: None,
+            });
+        }
+
+        if self.fallback {
+            entries.push(AbiEntry {
+                type_: String::from("fallback"),
+                name: None,
+                inputs: None,
+                outputs: None,
+                anonymous: None,
+                state_mutability: None,
+                constant: None,
+                payable: None,
+            });
+        }
+
+        if self.receive {
+            entries.push(AbiEntry {
+                type_: String::from("receive"),
+                name: None,
+                inputs: None,
+                outputs: None,
+                anonymous: None,
+                state_mutability: None,
+                constant: None,
+                payable: None,
             });
         }
+
         entries.serialize(serializer)
     }
 }
@@ -159,18 +674,74 @@ pub struct Function {
     pub inputs: Vec<Param>,
     /// Function outputs.
     pub outputs: Vec<Param>,
+    /// Function state mutability.
+    pub state_mutability: StateMutability,
+    /// Legacy `constant` flag, kept as read so that the ABI serializes back
+    /// unchanged.
+    pub constant: Option<bool>,
+    /// Legacy `payable` flag, kept as read so that the ABI serializes back
+    /// unchanged.
+    pub payable: Option<bool>,
+}
+
+/// Function state mutability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateMutability {
+    /// Does not read or modify contract state.
+    Pure,
+    /// Reads but does not modify contract state.
+    View,
+    /// Modifies contract state, does not accept value.
+    #[default]
+    NonPayable,
+    /// Modifies contract state and accepts value.
+    Payable,
+}
+
+impl StateMutability {
+    /// Returns whether the function leaves contract state untouched, i.e. can be
+    /// executed as a call rather than a transaction.
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, StateMutability::Pure | StateMutability::View)
+    }
+
+    /// Returns whether the function accepts value.
+    pub fn is_payable(&self) -> bool {
+        matches!(self, StateMutability::Payable)
+    }
 }
 
 impl Function {
+    /// Builds a function from its signature, e.g. `vote(u32,(address,string)[])`.
+    ///
+    /// The params are unnamed, the function has no outputs and is non-payable.
+    pub fn from_signature(signature: &str) -> Result<Self> {
+        let (name, inputs) = parse_signature(signature)?;
+
+        Ok(Function {
+            name,
+            inputs,
+            outputs: vec![],
+            state_mutability: StateMutability::NonPayable,
+            constant: None,
+            payable: None,
+        })
+    }
+
+    /// Returns whether the function leaves contract state untouched.
+    pub fn is_read_only(&self) -> bool {
+        self.state_mutability.is_read_only()
+    }
+
+    /// Returns whether the function accepts value.
+    pub fn is_payable(&self) -> bool {
+        self.state_mutability.is_payable()
+    }
+
     /// Computes the function's method id (function selector).
     pub fn method_id(&self) -> u64 {
-        use tiny_keccak::{Hasher, Keccak};
-
-        let mut keccak_out = [0u8; 32];
-        let mut hasher = Keccak::v256();
-        hasher.update(self.signature().as_bytes());
-        hasher.finalize(&mut keccak_out);
-        u32::from_be_bytes(keccak_out[0..4].try_into().unwrap()) as u64
+        selector(&self.signature())
     }
 
     /// Returns the function's signature.
@@ -180,14 +751,39 @@ impl Function {
             self.name,
             self.inputs
                 .iter()
-                .map(|param| param.type_.to_string())
+                .map(|param| param.type_.signature())
                 .collect::<Vec<_>>()
                 .join(",")
         )
     }
 
+    /// Checks that the given values match the function's inputs in number and type.
+    pub fn check_inputs(&self, values: &[Value]) -> Result<()> {
+        check_params(&self.inputs, values)
+    }
+
+    /// Encodes the function input as `[param1, param2, .. , param-len, method_id]`.
+    pub fn encode_input(&self, values: &[Value]) -> Result<Vec<u64>> {
+        self.check_inputs(values)?;
+
+        let mut params = Value::encode(values)?;
+        params.push(params.len() as u64);
+        params.push(self.method_id());
+
+        Ok(params)
+    }
+
     // Decode function input from slice.
     pub fn decode_input_from_slice(&self, input: &[u64]) -> Result<DecodedParams> {
+        self.decode_input_from_slice_with_options(input, &DecodeOptions::default())
+    }
+
+    /// Decode function input from slice using the given decoding options.
+    pub fn decode_input_from_slice_with_options(
+        &self,
+        input: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<DecodedParams> {
         let inputs_types = self
             .inputs
             .iter()
@@ -198,29 +794,181 @@ impl Function {
             self.inputs
                 .iter()
                 .cloned()
-                .zip(Value::decode_from_slice(input, &inputs_types)?)
+                .zip(Value::decode_from_slice_with_options(
+                    input,
+                    &inputs_types,
+                    opts,
+                )?)
                 .collect::<Vec<_>>(),
         ))
     }
 
-    // Decode function output from slice.
+    // Decode function output from slice, without the trailing length word.
     pub fn decode_output_from_slice(&self, output: &[u64]) -> Result<DecodedParams> {
-        let ouputs_types = self
+        self.decode_output_from_slice_with_options(output, &DecodeOptions::default())
+    }
+
+    /// Decode function output from slice, without the trailing length word, using
+    /// the given decoding options.
+    pub fn decode_output_from_slice_with_options(
+        &self,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<DecodedParams> {
+        let outputs_types = self
             .outputs
             .iter()
-            .map(|f_output| f_output.type_.clone())
+            .map(|f_output| f_output.type_.clone())
+            .collect::<Vec<_>>();
+
+        Ok(DecodedParams::from(
+            self.outputs
+                .iter()
+                .cloned()
+                .zip(Value::decode_from_slice_with_options(
+                    output,
+                    &outputs_types,
+                    opts,
+                )?)
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Decode function output from slice ending with the length word.
+    ///
+    /// output = [param1, param2, .. , param-len]
+    ///
+    /// Fails unless the outputs consume exactly `param-len` words and the length
+    /// word immediately follows them.
+    pub fn decode_output_with_len(&self, output: &[u64]) -> Result<DecodedParams> {
+        self.decode_output_with_len_with_options(output, &DecodeOptions::default())
+    }
+
+    /// Decode function output from slice ending with the length word, using the
+    /// given decoding options.
+    pub fn decode_output_with_len_with_options(
+        &self,
+        output: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<DecodedParams> {
+        let (&declared, params) = output.split_last().ok_or(AbiError::MissingLength)?;
+
+        let (decoded_params, decoded) = decode_params(&self.outputs, params, opts)?;
+        check_length_word(declared, decoded, params.len())?;
+
+        Ok(decoded_params)
+    }
+}
+
+/// Contract constructor definition.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Constructor {
+    /// Constructor inputs.
+    pub inputs: Vec<Param>,
+}
+
+impl Constructor {
+    /// Checks that the given values match the constructor's inputs in number and type.
+    pub fn check_inputs(&self, values: &[Value]) -> Result<()> {
+        check_params(&self.inputs, values)
+    }
+
+    /// Encodes the constructor arguments for a deployment payload.
+    ///
+    /// Unlike function calls, the encoding carries no method id:
+    /// `[param1, param2, .. , param-len]`.
+    pub fn encode_input(&self, values: &[Value]) -> Result<Vec<u64>> {
+        self.check_inputs(values)?;
+
+        let mut params = Value::encode(values)?;
+        params.push(params.len() as u64);
+
+        Ok(params)
+    }
+
+    /// Decode constructor input from slice, without the trailing length.
+    pub fn decode_input_from_slice(&self, input: &[u64]) -> Result<DecodedParams> {
+        self.decode_input_from_slice_with_options(input, &DecodeOptions::default())
+    }
+
+    /// Decode constructor input from slice, without the trailing length, using the
+    /// given decoding options.
+    pub fn decode_input_from_slice_with_options(
+        &self,
+        input: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<DecodedParams> {
+        let inputs_types = self
+            .inputs
+            .iter()
+            .map(|c_input| c_input.type_.clone())
             .collect::<Vec<_>>();
 
         Ok(DecodedParams::from(
-            self.outputs
+            self.inputs
                 .iter()
                 .cloned()
-                .zip(Value::decode_from_slice(output, &ouputs_types)?)
+                .zip(Value::decode_from_slice_with_options(
+                    input,
+                    &inputs_types,
+                    opts,
+                )?)
                 .collect::<Vec<_>>(),
         ))
     }
 }
 
+/// Decodes a list of params from the start of `data`, also returning the number
+/// of words consumed.
+fn decode_params(
+    params: &[Param],
+    data: &[u64],
+    opts: &DecodeOptions,
+) -> Result<(DecodedParams, usize)> {
+    let types = params
+        .iter()
+        .map(|param| param.type_.clone())
+        .collect::<Vec<_>>();
+
+    let (values, consumed) = Value::decode_from_slice_consumed(data, &types, opts)?;
+
+    Ok((
+        DecodedParams::from(params.iter().cloned().zip(values).collect::<Vec<_>>()),
+        consumed,
+    ))
+}
+
+/// Checks that values match a list of params in number and type.
+pub(crate) fn check_params(params: &[Param], values: &[Value]) -> Result<()> {
+    if values.len() != params.len() {
+        return Err(AbiError::LengthMismatch {
+            path: "inputs".to_string(),
+            expected: params.len(),
+            found: values.len(),
+        });
+    }
+
+    values
+        .iter()
+        .zip(params)
+        .enumerate()
+        .try_for_each(|(i, (value, param))| {
+            value.type_check(&param.type_, &format!("inputs[{}]", i))
+        })
+}
+
+/// Computes the selector of a function or error signature: the first four
+/// bytes of its Keccak-256 hash.
+pub(crate) fn selector(signature: &str) -> u64 {
+    use tiny_keccak::{Hasher, Keccak};
+
+    let mut keccak_out = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(signature.as_bytes());
+    hasher.finalize(&mut keccak_out);
+    u32::from_be_bytes(keccak_out[0..4].try_into().unwrap()) as u64
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AbiEntry {
@@ -234,6 +982,12 @@ struct AbiEntry {
     outputs: Option<Vec<Param>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     anonymous: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_mutability: Option<StateMutability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constant: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payable: Option<bool>,
 }
 
 struct AbiVisitor;
@@ -252,13 +1006,21 @@ impl<'de> Visitor<'de> for AbiVisitor {
         let mut abi = Abi {
             functions: vec![],
             events: vec![],
+            errors: vec![],
+            constructor: None,
+            fallback: false,
+            receive: false,
         };
 
         loop {
             let entry = seq.next_element::<AbiEntry>()?;
 
             match entry {
-                None => return Ok(abi),
+                None => {
+                    abi.validate().map_err(serde::de::Error::custom)?;
+
+                    return Ok(abi);
+                }
 
                 Some(entry) => match entry.type_.as_str() {
                     "function" => {
@@ -270,10 +1032,21 @@ impl<'de> Visitor<'de> for AbiVisitor {
                             serde::de::Error::custom("missing function name".to_string())
                         })?;
 
+                        // older ABIs carry `constant`/`payable` flags instead of `stateMutability`
+                        let state_mutability = match entry.state_mutability {
+                            Some(state_mutability) => state_mutability,
+                            None if entry.constant.unwrap_or(false) => StateMutability::View,
+                            None if entry.payable.unwrap_or(false) => StateMutability::Payable,
+                            None => StateMutability::NonPayable,
+                        };
+
                         abi.functions.push(Function {
                             name,
                             inputs,
                             outputs,
+                            state_mutability,
+                            constant: entry.constant,
+                            payable: entry.payable,
                         });
                     }
                     "event" => {
@@ -293,6 +1066,28 @@ impl<'de> Visitor<'de> for AbiVisitor {
                             anonymous,
                         });
                     }
+                    "error" => {
+                        let inputs = entry.inputs.unwrap_or_default();
+
+                        let name = entry.name.ok_or_else(|| {
+                            serde::de::Error::custom("missing error name".to_string())
+                        })?;
+
+                        abi.errors.push(Error { name, inputs });
+                    }
+                    "constructor" => {
+                        if abi.constructor.is_some() {
+                            return Err(serde::de::Error::custom(
+                                "duplicate constructor entry".to_string(),
+                            ));
+                        }
+
+                        let inputs = entry.inputs.unwrap_or_default();
+
+                        abi.constructor = Some(Constructor { inputs });
+                    }
+                    "fallback" => abi.fallback = true,
+                    "receive" => abi.receive = true,
 
                     _ => {
                         return Err(serde::de::Error::custom(format!(
@@ -391,14 +1186,19 @@ mod test {
                     name: "".to_string(),
                     type_: Type::Address,
                     indexed: None,
+                    internal_type: None,
                 },
                 Param {
                     name: "x".to_string(),
                     type_: Type::FixedArray(Box::new(Type::U32), 2),
                     indexed: None,
+                    internal_type: None,
                 },
             ],
             outputs: vec![],
+            state_mutability: StateMutability::NonPayable,
+            constant: None,
+            payable: None,
         }
     }
 
@@ -408,6 +1208,46 @@ mod test {
         assert_eq!(fun.signature(), "funname(address,u32[2])");
     }
 
+    #[test]
+    fn function_from_signature() {
+        let fun = Function::from_signature("funname(address,u32[2])").unwrap();
+        assert_eq!(
+            fun,
+            Function {
+                inputs: test_function()
+                    .inputs
+                    .into_iter()
+                    .map(|param| Param {
+                        name: "".to_string(),
+                        ..param
+                    })
+                    .collect(),
+                ..test_function()
+            }
+        );
+        assert_eq!(fun.method_id(), 0xf146ff09);
+
+        let signature = "vote(u32,(address,string)[],(u32,(bool,hash))[2])";
+        assert_eq!(
+            Function::from_signature(signature).unwrap().signature(),
+            signature
+        );
+        assert_eq!(Function::from_signature("f()").unwrap().inputs, vec![]);
+
+        for signature in [
+            "vote",
+            "vote(u32,)",
+            "vote(u33)",
+            "vote(u32) extra",
+            "(u32)",
+        ] {
+            assert_eq!(
+                Function::from_signature(signature).unwrap_err(),
+                AbiError::InvalidSignature(signature.to_string())
+            );
+        }
+    }
+
     #[test]
     fn function_method_id() {
         let fun = test_function();
@@ -429,9 +1269,13 @@ mod test {
         let abi = Abi {
             functions: vec![fun],
             events: vec![],
+            errors: vec![],
+            constructor: None,
+            fallback: false,
+            receive: false,
         };
 
-        let mut params = Value::encode(&input_values);
+        let mut params = Value::encode(&input_values).unwrap();
         params.push(params.len() as u64);
         params.push(abi.functions[0].method_id());
         let dec: (&Function, DecodedParams) = abi
@@ -450,6 +1294,156 @@ mod test {
         assert_eq!(dec, (&abi.functions[0], expected_decoded_params));
     }
 
+    #[test]
+    fn abi_encode_input_with_signature_type_check() {
+        let abi = Abi {
+            functions: vec![test_function()],
+            events: vec![],
+            errors: vec![],
+            constructor: None,
+            fallback: false,
+            receive: false,
+        };
+        let signature = "funname(address,u32[2])";
+
+        let encoded = abi
+            .encode_input_with_signature(
+                signature,
+                &[
+                    Value::Address(crate::FixedArray4([1, 2, 3, 4])),
+                    Value::FixedArray(vec![Value::U32(5), Value::U32(6)], Type::U32),
+                ],
+            )
+            .expect("encode_input_with_signature failed");
+        assert_eq!(
+            encoded,
+            vec![1, 2, 3, 4, 5, 6, 6, abi.functions[0].method_id()]
+        );
+
+        let err = abi
+            .encode_input_with_signature(signature, &[Value::Address(crate::FixedArray4([0; 4]))])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AbiError::LengthMismatch {
+                path: "inputs".to_string(),
+                expected: 2,
+                found: 1
+            }
+        );
+
+        let err = abi
+            .encode_input_with_signature(
+                signature,
+                &[
+                    Value::Address(crate::FixedArray4([0; 4])),
+                    Value::FixedArray(vec![Value::U32(5), Value::Field(6)], Type::U32),
+                ],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "type mismatch at inputs[1][1]: expected u32, found field"
+        );
+
+        let err = abi
+            .encode_input_with_signature(
+                signature,
+                &[
+                    Value::Address(crate::FixedArray4([0; 4])),
+                    Value::FixedArray(vec![Value::U32(5)], Type::U32),
+                ],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AbiError::LengthMismatch {
+                path: "inputs[1]".to_string(),
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn abi_decode_input_errors() {
+        let abi = Abi {
+            functions: vec![test_function()],
+            events: vec![],
+            errors: vec![],
+            constructor: None,
+            fallback: false,
+            receive: false,
+        };
+
+        assert_eq!(
+            abi.decode_input_from_slice(&[0]).unwrap_err(),
+            AbiError::MissingSelector
+        );
+        assert_eq!(
+            abi.decode_input_from_slice(&[0, 0x1234]).unwrap_err(),
+            AbiError::UnknownSelector(0x1234)
+        );
+        assert_eq!(
+            abi.decode_input_from_slice(&[1, 2, 3, 4, 5, 5, abi.functions[0].method_id()])
+                .unwrap_err(),
+            AbiError::UnexpectedEof {
+                ty: Type::U32,
+                offset: 5
+            }
+        );
+
+        let input = [1, 2, 3, 4, 5, 1 << 32, 6, abi.functions[0].method_id()];
+        assert!(abi.decode_input_from_slice(&input).is_ok());
+        assert_eq!(
+            abi.decode_input_from_slice_with_options(&input, &DecodeOptions::strict())
+                .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::U32,
+                offset: 5,
+                word: 1 << 32
+            }
+        );
+
+        let input = [1, 2, 3, 4, 5, 6, 9, abi.functions[0].method_id()];
+        assert!(abi.decode_input_from_slice(&input).is_ok());
+        assert_eq!(
+            abi.decode_input_from_slice_with_options(&input, &DecodeOptions::strict())
+                .unwrap_err(),
+            AbiError::LengthWordMismatch {
+                declared: 9,
+                decoded: 6,
+                available: 6
+            }
+        );
+        assert_eq!(
+            abi.index()
+                .decode_input_from_slice_with_options(&input, &DecodeOptions::strict())
+                .unwrap_err(),
+            AbiError::LengthWordMismatch {
+                declared: 9,
+                decoded: 6,
+                available: 6
+            }
+        );
+
+        let input = [1, 2, 3, 4, 5, 6, 7, 7, abi.functions[0].method_id()];
+        assert!(abi.decode_input_from_slice(&input).is_ok());
+        assert_eq!(
+            abi.decode_input_from_slice_with_options(&input, &DecodeOptions::strict())
+                .unwrap_err(),
+            AbiError::LengthWordMismatch {
+                declared: 7,
+                decoded: 6,
+                available: 7
+            }
+        );
+        assert_eq!(
+            abi.decode_output_from_slice("f()", &[]).unwrap_err(),
+            AbiError::UnknownSignature("f()".to_string())
+        );
+    }
+
     #[test]
     fn abi_json_work() {
         let v = serde_json::json!([
@@ -496,21 +1490,462 @@ mod test {
                             name: "n".to_string(),
                             type_: Type::U32,
                             indexed: None,
+                            internal_type: Some("u32".to_string()),
                         },
                         Param {
                             name: "x".to_string(),
-                            type_: Type::Tuple(vec![
-                                ("a".to_string(), Type::U32),
-                                ("b".to_string(), Type::String)
-                            ]),
+                            type_: Type::Struct {
+                                name: "A.X".to_string(),
+                                fields: vec![
+                                    ("a".to_string(), Type::U32),
+                                    ("b".to_string(), Type::String)
+                                ]
+                            },
                             indexed: None,
+                            internal_type: Some("struct A.X".to_string()),
                         }
                     ],
                     outputs: vec![],
+                    state_mutability: StateMutability::NonPayable,
+                    constant: None,
+                    payable: None,
                 }],
                 events: vec![],
+                errors: vec![],
+                constructor: None,
+                fallback: false,
+                receive: false,
+            }
+        );
+    }
+
+    #[test]
+    fn abi_errors() {
+        let v = serde_json::json!([
+            {
+                "type": "error",
+                "name": "InsufficientBalance",
+                "inputs": [
+                    {
+                        "name": "available",
+                        "type": "u32"
+                    },
+                    {
+                        "name": "required",
+                        "type": "u32"
+                    }
+                ]
+            }
+        ]);
+
+        let abi: Abi = serde_json::from_value(v.clone()).unwrap();
+        let available = Param {
+            name: "available".to_string(),
+            type_: Type::U32,
+            indexed: None,
+            internal_type: None,
+        };
+        let required = Param {
+            name: "required".to_string(),
+            type_: Type::U32,
+            indexed: None,
+            internal_type: None,
+        };
+        assert_eq!(
+            abi.errors,
+            vec![Error {
+                name: "InsufficientBalance".to_string(),
+                inputs: vec![available.clone(), required.clone()],
+            }]
+        );
+        assert_eq!(serde_json::to_value(&abi).unwrap(), v);
+
+        let err = &abi.errors[0];
+        assert_eq!(err.signature(), "InsufficientBalance(u32,u32)");
+
+        let data = vec![10, 20, 2, err.selector()];
+        assert_eq!(
+            abi.decode_error_from_slice(&data)
+                .expect("decode_error_from_slice failed"),
+            (
+                err,
+                DecodedParams::from(vec![
+                    (available, Value::U32(10)),
+                    (required, Value::U32(20))
+                ])
+            )
+        );
+
+        assert_eq!(
+            abi.decode_error_from_slice_with_options(
+                &[10, 20, 3, err.selector()],
+                &DecodeOptions::strict()
+            )
+            .unwrap_err(),
+            AbiError::LengthWordMismatch {
+                declared: 3,
+                decoded: 2,
+                available: 2
+            }
+        );
+        assert_eq!(
+            abi.decode_error_from_slice(&[2, 0x1234]).unwrap_err(),
+            AbiError::UnknownSelector(0x1234)
+        );
+    }
+
+    #[test]
+    fn abi_constructor_fallback_receive() {
+        let v = serde_json::json!([
+            {
+                "type": "constructor",
+                "inputs": [
+                    {
+                        "name": "owner",
+                        "type": "address"
+                    },
+                    {
+                        "name": "supply",
+                        "type": "u32"
+                    }
+                ]
+            },
+            {
+                "type": "fallback"
+            },
+            {
+                "type": "receive"
+            }
+        ]);
+
+        let abi: Abi = serde_json::from_value(v.clone()).unwrap();
+        assert!(abi.fallback);
+        assert!(abi.receive);
+        assert_eq!(serde_json::to_value(&abi).unwrap(), v);
+
+        let constructor = abi.constructor.as_ref().expect("missing constructor");
+        let input = constructor
+            .encode_input(&[
+                Value::Address(crate::FixedArray4([1, 2, 3, 4])),
+                Value::U32(100),
+            ])
+            .expect("encode_input failed");
+        assert_eq!(input, vec![1, 2, 3, 4, 100, 5]);
+
+        let decoded = constructor
+            .decode_input_from_slice(&input[..input.len() - 1])
+            .expect("decode_input_from_slice failed");
+        assert_eq!(decoded[1].value, Value::U32(100));
+
+        assert_eq!(
+            constructor.encode_input(&[Value::U32(100)]).unwrap_err(),
+            AbiError::LengthMismatch {
+                path: "inputs".to_string(),
+                expected: 2,
+                found: 1
+            }
+        );
+
+        let duplicate = serde_json::json!([
+            { "type": "constructor", "inputs": [] },
+            { "type": "constructor", "inputs": [] }
+        ]);
+        assert!(serde_json::from_value::<Abi>(duplicate).is_err());
+    }
+
+    #[test]
+    fn abi_state_mutability() {
+        let v = serde_json::json!([
+            {
+                "type": "function",
+                "name": "balance",
+                "inputs": [],
+                "outputs": [],
+                "stateMutability": "view"
+            },
+            {
+                "type": "function",
+                "name": "legacyGetter",
+                "inputs": [],
+                "outputs": [],
+                "constant": true
+            },
+            {
+                "type": "function",
+                "name": "legacyDeposit",
+                "inputs": [],
+                "outputs": [],
+                "payable": true
+            },
+            {
+                "type": "function",
+                "name": "transfer",
+                "inputs": [],
+                "outputs": []
+            }
+        ]);
+
+        let abi: Abi = serde_json::from_value(v).unwrap();
+        assert_eq!(
+            abi.functions
+                .iter()
+                .map(|f| f.state_mutability)
+                .collect::<Vec<_>>(),
+            vec![
+                StateMutability::View,
+                StateMutability::View,
+                StateMutability::Payable,
+                StateMutability::NonPayable
+            ]
+        );
+        assert!(abi.functions[0].is_read_only());
+        assert!(!abi.functions[2].is_read_only());
+        assert!(abi.functions[2].is_payable());
+
+        let ser = serde_json::to_value(&abi).unwrap();
+        assert_eq!(ser[1]["stateMutability"], "view");
+        assert_eq!(ser[1]["constant"], true);
+        assert_eq!(ser[2]["payable"], true);
+        assert_eq!(ser[3]["stateMutability"], "nonpayable");
+        assert!(ser[0].get("constant").is_none());
+
+        let de_abi: Abi = serde_json::from_value(ser).unwrap();
+        assert_eq!(abi, de_abi);
+    }
+
+    #[test]
+    fn abi_index() {
+        let abi: Abi = serde_json::from_str(TEST_ABI).unwrap();
+        let index = abi.index();
+
+        let vote = &abi.functions[3];
+        assert_eq!(
+            index.function_by_signature("vote_proposal(u32)").unwrap(),
+            vote
+        );
+        assert_eq!(index.function_by_method_id(vote.method_id()).unwrap(), vote);
+
+        let input = index
+            .encode_input_with_signature("vote_proposal(u32)", &[Value::U32(7)])
+            .expect("encode_input_with_signature failed");
+        assert_eq!(
+            index.decode_input_from_slice(&input).unwrap(),
+            abi.decode_input_from_slice(&input).unwrap()
+        );
+
+        assert_eq!(
+            index.decode_input_from_slice(&[0, 1]).unwrap_err(),
+            AbiError::UnknownSelector(1)
+        );
+        assert_eq!(
+            index.decode_log_from_slice(&[], &[]).unwrap_err(),
+            AbiError::MissingTopic
+        );
+    }
+
+    #[test]
+    fn abi_index_duplicates() {
+        let u32_param = |name: &str| Param {
+            name: name.to_string(),
+            type_: Type::U32,
+            indexed: None,
+            internal_type: None,
+        };
+
+        let first = test_function();
+        let second = Function {
+            outputs: vec![u32_param("ok")],
+            ..test_function()
+        };
+        let voted = |name| Event {
+            name: "Voted".to_string(),
+            inputs: vec![u32_param(name)],
+            anonymous: false,
+        };
+        let failed = |name| Error {
+            name: "Failed".to_string(),
+            inputs: vec![u32_param(name)],
+        };
+
+        let abi = Abi {
+            functions: vec![first, second],
+            events: vec![voted("a"), voted("b")],
+            errors: vec![failed("a"), failed("b")],
+            constructor: None,
+            fallback: false,
+            receive: false,
+        };
+        let index = abi.index();
+
+        // the first declared entry wins, as with the lookups of `Abi`.
+        let f = &abi.functions[0];
+        assert_eq!(index.function_by_method_id(f.method_id()).unwrap(), f);
+        assert_eq!(index.function_by_signature(&f.signature()).unwrap(), f);
+        assert_eq!(abi.function(&f.signature()).unwrap(), f);
+
+        let e = &abi.events[0];
+        assert_eq!(index.event_by_topic(&e.topic()).unwrap(), e);
+        let topics = [e.topic()];
+        assert_eq!(
+            index.decode_log_from_slice(&topics, &[1]).unwrap(),
+            abi.decode_log_from_slice(&topics, &[1]).unwrap()
+        );
+
+        let e = &abi.errors[0];
+        assert_eq!(index.error_by_selector(e.selector()).unwrap(), e);
+        let data = [1, 1, e.selector()];
+        assert_eq!(
+            index.decode_error_from_slice(&data).unwrap(),
+            abi.decode_error_from_slice(&data).unwrap()
+        );
+        assert_eq!(abi.decode_error_from_slice(&data).unwrap().0, e);
+    }
+
+    #[test]
+    fn abi_overloaded_functions() {
+        let v = serde_json::json!([
+            {
+                "type": "function",
+                "name": "vote",
+                "inputs": [{ "name": "proposal", "type": "u32" }],
+                "outputs": []
+            },
+            {
+                "type": "function",
+                "name": "vote",
+                "inputs": [{ "name": "proposal", "type": "string" }],
+                "outputs": []
+            },
+            {
+                "type": "function",
+                "name": "winner",
+                "inputs": [],
+                "outputs": []
+            }
+        ]);
+
+        let abi: Abi = serde_json::from_value(v).unwrap();
+
+        assert_eq!(abi.functions_by_name("vote").len(), 2);
+        assert_eq!(abi.function("winner").unwrap(), &abi.functions[2]);
+        assert_eq!(abi.function("vote(string)").unwrap(), &abi.functions[1]);
+        assert_eq!(
+            abi.function("vote").unwrap_err(),
+            AbiError::AmbiguousFunction {
+                name: "vote".to_string(),
+                signatures: vec!["vote(u32)".to_string(), "vote(string)".to_string()]
+            }
+        );
+        assert_eq!(
+            abi.function("missing").unwrap_err(),
+            AbiError::UnknownFunction("missing".to_string())
+        );
+    }
+
+    #[test]
+    fn abi_validate_collisions() {
+        let mut abi = Abi {
+            functions: vec![test_function(), test_function()],
+            events: vec![],
+            errors: vec![],
+            constructor: None,
+            fallback: false,
+            receive: false,
+        };
+
+        assert_eq!(
+            abi.validate().unwrap_err(),
+            AbiError::SelectorCollision {
+                selector: 0xf146ff09,
+                first: "funname(address,u32[2])".to_string(),
+                second: "funname(address,u32[2])".to_string()
+            }
+        );
+
+        let event = Event {
+            name: "Voted".to_string(),
+            inputs: vec![],
+            anonymous: false,
+        };
+        abi.functions.pop();
+        abi.events = vec![event.clone(), event.clone()];
+        assert_eq!(
+            abi.validate().unwrap_err(),
+            AbiError::TopicCollision {
+                topic: event.topic(),
+                first: "Voted()".to_string(),
+                second: "Voted()".to_string()
+            }
+        );
+
+        let ser = serde_json::to_string(&abi).unwrap();
+        assert!(serde_json::from_str::<Abi>(&ser).is_err());
+    }
+
+    #[test]
+    fn abi_decode_output() {
+        let abi: Abi = serde_json::from_str(TEST_ABI).unwrap();
+        let f = &abi.functions[1];
+        let expected = DecodedParams::from(vec![(f.outputs[0].clone(), Value::U32(7))]);
+
+        assert_eq!(
+            abi.decode_output_from_slice("winningProposal()", &[7, 1])
+                .unwrap(),
+            (f, expected.clone())
+        );
+        assert_eq!(
+            abi.decode_output_by_method_id(f.method_id(), &[7, 1])
+                .unwrap(),
+            (f, expected.clone())
+        );
+        assert_eq!(
+            abi.index()
+                .decode_output_by_method_id(f.method_id(), &[7, 1])
+                .unwrap(),
+            (f, expected.clone())
+        );
+        assert_eq!(f.decode_output_from_slice(&[7]).unwrap(), expected);
+
+        assert_eq!(
+            f.decode_output_with_len(&[]).unwrap_err(),
+            AbiError::MissingLength
+        );
+        assert_eq!(
+            f.decode_output_with_len(&[7, 2]).unwrap_err(),
+            AbiError::LengthWordMismatch {
+                declared: 2,
+                decoded: 1,
+                available: 1
             }
         );
+        assert_eq!(
+            f.decode_output_with_len(&[7, 8, 1]).unwrap_err(),
+            AbiError::LengthWordMismatch {
+                declared: 1,
+                decoded: 1,
+                available: 2
+            }
+        );
+        assert!(abi
+            .decode_output_by_method_id(f.method_id(), &[1 << 32, 1])
+            .is_ok());
+        assert_eq!(
+            abi.decode_output_by_method_id_with_options(
+                f.method_id(),
+                &[1 << 32, 1],
+                &DecodeOptions::strict()
+            )
+            .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::U32,
+                offset: 0,
+                word: 1 << 32
+            }
+        );
+        assert_eq!(
+            abi.decode_output_by_method_id(0x1234, &[7, 1]).unwrap_err(),
+            AbiError::UnknownSelector(0x1234)
+        );
     }
 
     #[test]
@@ -0,0 +1,360 @@
+use crate::{
+    error::{AbiError, Result},
+    types::Type,
+    values::{FixedArray8, Value},
+};
+
+/// Rust types that map to a single ABI type.
+///
+/// This lets `Vec<T>` and `[T; N]` convert to array values carrying their
+/// element type, even when empty. Rust tuples map to tuples with unnamed fields,
+/// which match struct and named tuple params by position only.
+pub trait AbiType {
+    /// Returns the ABI type of the Rust type.
+    fn abi_type() -> Type;
+}
+
+/// Implements [`AbiType`] and the conversions from and to [`Value`] for a
+/// scalar Rust type.
+macro_rules! impl_scalar {
+    ($rust_ty:ty, $variant:ident, $ty:expr) => {
+        impl AbiType for $rust_ty {
+            fn abi_type() -> Type {
+                $ty
+            }
+        }
+
+        impl From<$rust_ty> for Value {
+            fn from(value: $rust_ty) -> Self {
+                Value::$variant(value.into())
+            }
+        }
+
+        impl TryFrom<Value> for $rust_ty {
+            type Error = AbiError;
+
+            fn try_from(value: Value) -> Result<Self> {
+                match value {
+                    Value::$variant(inner) => {
+                        inner.try_into().map_err(|_| AbiError::InvalidValue {
+                            path: "$".to_string(),
+                            ty: Box::new($ty),
+                            input: inner.to_string(),
+                        })
+                    }
+                    value => Err(mismatch($ty, &value)),
+                }
+            }
+        }
+    };
+}
+
+impl_scalar!(u8, U8, Type::U8);
+impl_scalar!(u16, U16, Type::U16);
+impl_scalar!(u32, U32, Type::U32);
+impl_scalar!(u64, U64, Type::U64);
+impl_scalar!(i32, I32, Type::I32);
+impl_scalar!(i64, I64, Type::I64);
+impl_scalar!(bool, Bool, Type::Bool);
+impl_scalar!(FixedArray8, U256, Type::U256);
+
+impl AbiType for String {
+    fn abi_type() -> Type {
+        Type::String
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = AbiError;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::String(s) => Ok(s),
+            value => Err(mismatch(Type::String, &value)),
+        }
+    }
+}
+
+impl AbiType for &str {
+    fn abi_type() -> Type {
+        Type::String
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl<T: AbiType> AbiType for Vec<T> {
+    fn abi_type() -> Type {
+        Type::Array(Box::new(T::abi_type()))
+    }
+}
+
+/// Converts to a [`Value::Array`]; see [`Value::from_field_elements`] for
+/// `fields` values.
+impl<T: AbiType + Into<Value>> From<Vec<T>> for Value {
+    fn from(values: Vec<T>) -> Self {
+        Value::Array(values.into_iter().map(Into::into).collect(), T::abi_type())
+    }
+}
+
+impl<T: AbiType + TryFrom<Value, Error = AbiError>> TryFrom<Value> for Vec<T> {
+    type Error = AbiError;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Array(values, _) | Value::FixedArray(values, _) => try_from_elements(values),
+            value => Err(mismatch(Self::abi_type(), &value)),
+        }
+    }
+}
+
+impl<T: AbiType, const N: usize> AbiType for [T; N] {
+    fn abi_type() -> Type {
+        Type::FixedArray(Box::new(T::abi_type()), N as u64)
+    }
+}
+
+impl<T: AbiType + Into<Value>, const N: usize> From<[T; N]> for Value {
+    fn from(values: [T; N]) -> Self {
+        Value::FixedArray(values.into_iter().map(Into::into).collect(), T::abi_type())
+    }
+}
+
+impl<T: AbiType + TryFrom<Value, Error = AbiError>, const N: usize> TryFrom<Value> for [T; N] {
+    type Error = AbiError;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::FixedArray(values, _) if values.len() == N => {
+                try_from_elements(values).map(|values: Vec<T>| {
+                    values
+                        .try_into()
+                        .unwrap_or_else(|_| unreachable!("length checked above"))
+                })
+            }
+            Value::FixedArray(values, _) => Err(AbiError::LengthMismatch {
+                path: "$".to_string(),
+                expected: N,
+                found: values.len(),
+            }),
+            value => Err(mismatch(Self::abi_type(), &value)),
+        }
+    }
+}
+
+/// Implements [`AbiType`] and the conversions from and to [`Value`] for a tuple
+/// of the given arity, with unnamed fields.
+macro_rules! impl_tuple {
+    ($len:expr; $($name:ident $index:tt),+) => {
+        impl<$($name: AbiType),+> AbiType for ($($name,)+) {
+            fn abi_type() -> Type {
+                Type::Tuple(vec![$((String::new(), $name::abi_type())),+])
+            }
+        }
+
+        /// Converts to a [`Value::Tuple`] with unnamed fields.
+        impl<$($name: Into<Value>),+> From<($($name,)+)> for Value {
+            fn from(values: ($($name,)+)) -> Self {
+                Value::Tuple(vec![$((String::new(), values.$index.into())),+])
+            }
+        }
+
+        impl<$($name: AbiType + TryFrom<Value, Error = AbiError>),+> TryFrom<Value>
+            for ($($name,)+)
+        {
+            type Error = AbiError;
+
+            fn try_from(value: Value) -> Result<Self> {
+                match value {
+                    Value::Tuple(fields) if fields.len() == $len => {
+                        let mut values = fields.into_iter().map(|(_, value)| value);
+
+                        Ok(($(
+                            $name::try_from(values.next().expect("length checked above"))
+                                .map_err(|err| nest(err, concat!(".", $index)))?,
+                        )+))
+                    }
+                    Value::Tuple(fields) => Err(AbiError::LengthMismatch {
+                        path: "$".to_string(),
+                        expected: $len,
+                        found: fields.len(),
+                    }),
+                    value => Err(mismatch(Self::abi_type(), &value)),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple!(1; A 0);
+impl_tuple!(2; A 0, B 1);
+impl_tuple!(3; A 0, B 1, C 2);
+impl_tuple!(4; A 0, B 1, C 2, D 3);
+impl_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(11; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(12; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+fn try_from_elements<T: TryFrom<Value, Error = AbiError>>(values: Vec<Value>) -> Result<Vec<T>> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| T::try_from(value).map_err(|err| nest(err, &format!("[{}]", i))))
+        .collect()
+}
+
+fn mismatch(expected: Type, value: &Value) -> AbiError {
+    AbiError::TypeMismatch {
+        path: "$".to_string(),
+        expected,
+        found: value.type_of(),
+    }
+}
+
+/// Prefixes the path of a conversion error raised on a nested value with the
+/// given path segment, e.g. `[1]` or `.0`.
+fn nest(err: AbiError, segment: &str) -> AbiError {
+    let nest_path = |path: String| format!("${}{}", segment, &path[1..]);
+
+    match err {
+        AbiError::TypeMismatch {
+            path,
+            expected,
+            found,
+        } => AbiError::TypeMismatch {
+            path: nest_path(path),
+            expected,
+            found,
+        },
+        AbiError::LengthMismatch {
+            path,
+            expected,
+            found,
+        } => AbiError::LengthMismatch {
+            path: nest_path(path),
+            expected,
+            found,
+        },
+        AbiError::InvalidValue { path, ty, input } => AbiError::InvalidValue {
+            path: nest_path(path),
+            ty,
+            input,
+        },
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::Function;
+
+    #[test]
+    fn into_value() {
+        assert_eq!(Value::from(7u32), Value::U32(7));
+        assert_eq!(Value::from(-7i64), Value::I64(-7));
+        assert_eq!(Value::from("x"), Value::String("x".to_string()));
+        assert_eq!(
+            Value::from(Vec::<u8>::new()),
+            Value::Array(vec![], Type::U8)
+        );
+        assert_eq!(
+            Value::from([vec![true], vec![]]),
+            Value::FixedArray(
+                vec![
+                    Value::Array(vec![Value::Bool(true)], Type::Bool),
+                    Value::Array(vec![], Type::Bool),
+                ],
+                Type::Array(Box::new(Type::Bool))
+            )
+        );
+        assert_eq!(
+            Value::from((1u16, "a", [2u64; 1])),
+            Value::Tuple(vec![
+                ("".to_string(), Value::U16(1)),
+                ("".to_string(), Value::String("a".to_string())),
+                (
+                    "".to_string(),
+                    Value::FixedArray(vec![Value::U64(2)], Type::U64)
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn encode_converted_values() {
+        let function = Function::from_signature("f(u32,string,(bool,u256)[])").unwrap();
+
+        let values = vec![
+            7u32.into(),
+            "x".into(),
+            vec![(true, FixedArray8::from(1u64))].into(),
+        ];
+
+        assert!(function.check_inputs(&values).is_ok());
+    }
+
+    #[test]
+    fn try_from_value() {
+        let value = Value::from((vec![1u32, 2], (String::from("a"), [true, false])));
+
+        let (ids, (name, flags)): (Vec<u32>, (String, [bool; 2])) = value.try_into().unwrap();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(name, "a");
+        assert_eq!(flags, [true, false]);
+    }
+
+    #[test]
+    fn try_from_value_errors() {
+        assert_eq!(
+            u32::try_from(Value::U32(1 << 32)).unwrap_err(),
+            AbiError::InvalidValue {
+                path: "$".to_string(),
+                ty: Box::new(Type::U32),
+                input: (1u64 << 32).to_string(),
+            }
+        );
+        assert_eq!(
+            <(u32, Vec<bool>)>::try_from(Value::from((1u32, vec![1u8]))).unwrap_err(),
+            AbiError::TypeMismatch {
+                path: "$.1[0]".to_string(),
+                expected: Type::Bool,
+                found: Type::U8,
+            }
+        );
+        assert_eq!(
+            <[u8; 3]>::try_from(Value::from([1u8, 2])).unwrap_err(),
+            AbiError::LengthMismatch {
+                path: "$".to_string(),
+                expected: 3,
+                found: 2,
+            }
+        );
+        assert_eq!(
+            <(u8, u8)>::try_from(Value::from("x")).unwrap_err(),
+            AbiError::TypeMismatch {
+                path: "$".to_string(),
+                expected: Type::Tuple(
+                    vec![("".to_string(), Type::U8), ("".to_string(), Type::U8),]
+                ),
+                found: Type::String,
+            }
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use std::fmt;
+
+use crate::{FixedArray4, Type};
+
+pub(crate) type Result<T, E = AbiError> = std::result::Result<T, E>;
+
+/// Errors returned when encoding or decoding ABI data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiError {
+    /// No function matches the given method id.
+    UnknownSelector(u64),
+    /// No function matches the given signature.
+    UnknownSignature(String),
+    /// No event matches the given topic.
+    UnknownTopic(FixedArray4),
+    /// No function has the given name.
+    UnknownFunction(String),
+    /// Several overloaded functions share the given name.
+    AmbiguousFunction {
+        /// Function name.
+        name: String,
+        /// Signatures of the matching overloads.
+        signatures: Vec<String>,
+    },
+    /// No param matches the given name or position.
+    UnknownParam(String),
+    /// The given event param is not indexed, so it cannot be filtered on.
+    NotIndexed(String),
+    /// Two functions or two errors share the same selector.
+    SelectorCollision {
+        /// Colliding selector.
+        selector: u64,
+        /// Signature of the first entry.
+        first: String,
+        /// Signature of the second entry.
+        second: String,
+    },
+    /// Two events share the same topic.
+    TopicCollision {
+        /// Colliding topic.
+        topic: FixedArray4,
+        /// Signature of the first event.
+        first: String,
+        /// Signature of the second event.
+        second: String,
+    },
+    /// A human-readable declaration could not be parsed.
+    InvalidDeclaration(String),
+    /// A function or event signature could not be parsed.
+    InvalidSignature(String),
+    /// The input is too short to hold the trailing length word and method id.
+    MissingSelector,
+    /// The output is empty, so it lacks the trailing length word.
+    MissingLength,
+    /// The length word of an input, output or error does not match the decoded params.
+    LengthWordMismatch {
+        /// Length declared by the length word.
+        declared: u64,
+        /// Number of words consumed by decoding the params.
+        decoded: usize,
+        /// Number of words before the length word.
+        available: usize,
+    },
+    /// The log has no topic although the event is not anonymous.
+    MissingTopic,
+    /// The log has fewer topics than the event has indexed params.
+    InsufficientTopics {
+        /// Number of topics the event requires.
+        expected: usize,
+        /// Number of topics found.
+        found: usize,
+    },
+    /// The input ended before a value of the given type could be decoded.
+    UnexpectedEof {
+        /// Type being decoded.
+        ty: Type,
+        /// Offset of the value in the input.
+        offset: usize,
+    },
+    /// A word is not the canonical encoding of a value of the given type.
+    NonCanonical {
+        /// Type being decoded.
+        ty: Type,
+        /// Offset of the word in the input.
+        offset: usize,
+        /// Offending word.
+        word: u64,
+    },
+    /// The input holds words past the decoded values.
+    TrailingData {
+        /// Offset of the first unconsumed word.
+        offset: usize,
+    },
+    /// An enum discriminant does not match any variant.
+    InvalidDiscriminant {
+        /// Enum name.
+        name: String,
+        /// Offset of the discriminant in the input.
+        offset: usize,
+        /// Offending discriminant.
+        discriminant: u64,
+    },
+    /// A value is not a canonical Goldilocks field element.
+    InvalidField(u64),
+    /// A `u256` limb does not fit in 32 bits.
+    InvalidLimb {
+        /// Index of the limb, most significant first.
+        index: usize,
+        /// Offending limb.
+        limb: u64,
+    },
+    /// A string value does not hold valid UTF-8.
+    InvalidUtf8 {
+        /// Offset of the string in the input.
+        offset: usize,
+    },
+    /// An input cannot be converted to a value of the declared type.
+    InvalidValue {
+        /// Path to the offending value, e.g. `$.books[1].id`.
+        path: String,
+        /// Declared type.
+        ty: Box<Type>,
+        /// Offending input.
+        input: String,
+    },
+    /// A value does not match the declared type.
+    TypeMismatch {
+        /// Path to the offending value, e.g. `inputs[1].x[3]`.
+        path: String,
+        /// Declared type.
+        expected: Type,
+        /// Type of the given value.
+        found: Type,
+    },
+    /// A list of values does not have the declared number of elements.
+    LengthMismatch {
+        /// Path to the offending value, or `inputs` for function arguments.
+        path: String,
+        /// Declared number of elements.
+        expected: usize,
+        /// Number of elements given.
+        found: usize,
+    },
+    /// An enum value names a variant the declared enum does not have.
+    UnknownVariant {
+        /// Path to the offending value.
+        path: String,
+        /// Enum name.
+        name: String,
+        /// Given variant name.
+        variant: String,
+    },
+    /// A tuple field name does not match the declared one.
+    FieldMismatch {
+        /// Path to the offending tuple.
+        path: String,
+        /// Declared field name.
+        expected: String,
+        /// Given field name.
+        found: String,
+    },
+}
+
+impl fmt::Display for AbiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiError::UnknownSelector(method_id) => {
+                write!(
+                    f,
+                    "ABI function not found for method id {:#010x}",
+                    method_id
+                )
+            }
+            AbiError::UnknownSignature(signature) => {
+                write!(f, "ABI function not found for signature {}", signature)
+            }
+            AbiError::UnknownTopic(topic) => write!(f, "ABI event not found for topic {}", topic),
+            AbiError::UnknownFunction(name) => write!(f, "ABI function not found: {}", name),
+            AbiError::AmbiguousFunction { name, signatures } => write!(
+                f,
+                "ambiguous function name {}: matches {}",
+                name,
+                signatures.join(", ")
+            ),
+            AbiError::UnknownParam(param) => write!(f, "param not found: {}", param),
+            AbiError::NotIndexed(param) => write!(f, "param is not indexed: {}", param),
+            AbiError::SelectorCollision {
+                selector,
+                first,
+                second,
+            } => write!(
+                f,
+                "selector collision {:#010x} between {} and {}",
+                selector, first, second
+            ),
+            AbiError::TopicCollision {
+                topic,
+                first,
+                second,
+            } => write!(
+                f,
+                "topic collision {} between {} and {}",
+                topic, first, second
+            ),
+            AbiError::InvalidDeclaration(declaration) => {
+                write!(f, "invalid ABI declaration: {}", declaration)
+            }
+            AbiError::InvalidSignature(signature) => {
+                write!(f, "invalid signature: {}", signature)
+            }
+            AbiError::MissingSelector => write!(f, "missing function method id"),
+            AbiError::MissingLength => write!(f, "missing output length"),
+            AbiError::LengthWordMismatch {
+                declared,
+                decoded,
+                available,
+            } => write!(
+                f,
+                "length word mismatch: declared {}, decoded {} of {} words",
+                declared, decoded, available
+            ),
+            AbiError::MissingTopic => write!(f, "missing event topic"),
+            AbiError::InsufficientTopics { expected, found } => write!(
+                f,
+                "insufficient topics entries: expected {}, found {}",
+                expected, found
+            ),
+            AbiError::UnexpectedEof { ty, offset } => write!(
+                f,
+                "reached end of input while decoding {} at offset {}",
+                ty, offset
+            ),
+            AbiError::NonCanonical { ty, offset, word } => write!(
+                f,
+                "non-canonical {} encoding at offset {}: {}",
+                ty, offset, word
+            ),
+            AbiError::TrailingData { offset } => {
+                write!(f, "unexpected trailing data at offset {}", offset)
+            }
+            AbiError::InvalidDiscriminant {
+                name,
+                offset,
+                discriminant,
+            } => write!(
+                f,
+                "invalid discriminant {} of enum {} at offset {}",
+                discriminant, name, offset
+            ),
+            AbiError::InvalidField(value) => {
+                write!(f, "value {} is not a canonical field element", value)
+            }
+            AbiError::InvalidLimb { index, limb } => {
+                write!(f, "u256 limb {} does not fit in 32 bits: {}", index, limb)
+            }
+            AbiError::InvalidUtf8 { offset } => {
+                write!(f, "invalid utf-8 string at offset {}", offset)
+            }
+            AbiError::InvalidValue { path, ty, input } => {
+                write!(f, "invalid {} value at {}: {}", ty, path, input)
+            }
+            AbiError::TypeMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "type mismatch at {}: expected {}, found {}",
+                path, expected, found
+            ),
+            AbiError::LengthMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "length mismatch at {}: expected {} elements, found {}",
+                path, expected, found
+            ),
+            AbiError::UnknownVariant {
+                path,
+                name,
+                variant,
+            } => write!(
+                f,
+                "unknown variant `{}` of enum {} at {}",
+                variant, name, path
+            ),
+            AbiError::FieldMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "field mismatch at {}: expected field `{}`, found `{}`",
+                path, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AbiError {}
+
+/// Errors returned when parsing a hex string into a fixed size array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromHexError {
+    /// The string contains a character that is not a hex digit.
+    InvalidChar {
+        /// Offending character.
+        c: char,
+        /// Byte offset of the character in the string.
+        index: usize,
+    },
+    /// The string holds more hex digits than fit in the array.
+    TooLong {
+        /// Maximum number of hex digits.
+        max: usize,
+        /// Number of hex digits found.
+        found: usize,
+    },
+    /// The string does not hold exactly the required number of hex digits.
+    InvalidLength {
+        /// Required number of hex digits.
+        expected: usize,
+        /// Number of hex digits found.
+        found: usize,
+    },
+}
+
+impl fmt::Display for FromHexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromHexError::InvalidChar { c, index } => {
+                write!(f, "invalid hex character {:?} at index {}", c, index)
+            }
+            FromHexError::TooLong { max, found } => write!(
+                f,
+                "hex string too long: expected at most {} digits, found {}",
+                max, found
+            ),
+            FromHexError::InvalidLength { expected, found } => write!(
+                f,
+                "invalid hex string length: expected {} digits, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FromHexError {}
+
+/// Errors returned when parsing a decimal string into a 256-bit value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromDecError {
+    /// The string is empty.
+    Empty,
+    /// The string contains a character that is not a decimal digit.
+    InvalidChar {
+        /// Offending character.
+        c: char,
+        /// Byte offset of the character in the string.
+        index: usize,
+    },
+    /// The value does not fit in 256 bits.
+    Overflow,
+}
+
+impl fmt::Display for FromDecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromDecError::Empty => write!(f, "empty decimal string"),
+            FromDecError::InvalidChar { c, index } => {
+                write!(f, "invalid decimal character {:?} at index {}", c, index)
+            }
+            FromDecError::Overflow => write!(f, "decimal value does not fit in 256 bits"),
+        }
+    }
+}
+
+impl std::error::Error for FromDecError {}
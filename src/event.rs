@@ -1,8 +1,12 @@
-use anyhow::{anyhow, Result};
 use mini_goldilocks::poseidon::unsafe_poseidon_bytes_auto_padded;
 use std::collections::VecDeque;
 
-use crate::{DecodedParams, FixedArray4, Param, Type, Value};
+use crate::{
+    abi::{check_params, selector},
+    error::{AbiError, Result},
+    human_readable::parse_signature,
+    DecodeOptions, DecodedParams, FixedArray4, Param, Type, Value,
+};
 
 /// Contract Error Definition
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -13,6 +17,57 @@ pub struct Error {
     pub inputs: Vec<Param>,
 }
 
+impl Error {
+    /// Returns the error's signature.
+    pub fn signature(&self) -> String {
+        format!(
+            "{}({})",
+            self.name,
+            self.inputs
+                .iter()
+                .map(|param| param.type_.signature())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+
+    /// Computes the error's selector.
+    pub fn selector(&self) -> u64 {
+        selector(&self.signature())
+    }
+
+    /// Decode error params from revert data, without the trailing length and selector.
+    pub fn decode_data_from_slice(&self, data: &[u64]) -> Result<DecodedParams> {
+        self.decode_data_from_slice_with_options(data, &DecodeOptions::default())
+    }
+
+    /// Decode error params from revert data, without the trailing length and
+    /// selector, using the given decoding options.
+    pub fn decode_data_from_slice_with_options(
+        &self,
+        data: &[u64],
+        opts: &DecodeOptions,
+    ) -> Result<DecodedParams> {
+        let inputs_types = self
+            .inputs
+            .iter()
+            .map(|input| input.type_.clone())
+            .collect::<Vec<_>>();
+
+        Ok(DecodedParams::from(
+            self.inputs
+                .iter()
+                .cloned()
+                .zip(Value::decode_from_slice_with_options(
+                    data,
+                    &inputs_types,
+                    opts,
+                )?)
+                .collect::<Vec<_>>(),
+        ))
+    }
+}
+
 /// Contract event definition.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Event {
@@ -25,6 +80,19 @@ pub struct Event {
 }
 
 impl Event {
+    /// Builds an event from its signature, e.g. `Transfer(address,address,u256)`.
+    ///
+    /// The params are unnamed and not indexed, and the event is not anonymous.
+    pub fn from_signature(signature: &str) -> Result<Self> {
+        let (name, inputs) = parse_signature(signature)?;
+
+        Ok(Event {
+            name,
+            inputs,
+            anonymous: false,
+        })
+    }
+
     /// Returns the event's signature.
     pub fn signature(&self) -> String {
         format!(
@@ -32,38 +100,60 @@ impl Event {
             self.name,
             self.inputs
                 .iter()
-                .map(|param| param.type_.to_string())
+                .map(|param| param.type_.signature())
                 .collect::<Vec<_>>()
                 .join(",")
         )
     }
 
     /// Compute the event's topic hash
-
     pub fn topic(&self) -> FixedArray4 {
         FixedArray4(unsafe_poseidon_bytes_auto_padded(
-            &self.signature().as_bytes(),
+            self.signature().as_bytes(),
         ))
     }
 
     /// Decode event params from a log's topics and data.
     pub fn decode_data_from_slice(
+        &self,
+        topics: &[FixedArray4],
+        data: &[u64],
+    ) -> Result<DecodedParams> {
+        self.decode_data_from_slice_with_options(topics, data, &DecodeOptions::default())
+    }
+
+    /// Decode event params from a log's topics and data using the given decoding
+    /// options.
+    ///
+    /// In strict mode, values packed into topics must also be canonical.
+    pub fn decode_data_from_slice_with_options(
         &self,
         mut topics: &[FixedArray4],
         data: &[u64],
+        opts: &DecodeOptions,
     ) -> Result<DecodedParams> {
         // strip event topic from the topics array
         // so that we end up with only the values we
         // need to decode
         if !self.anonymous {
-            topics = topics
-                .get(1..)
-                .ok_or_else(|| anyhow!("missing event topic"))?;
+            topics = topics.get(1..).ok_or(AbiError::MissingTopic)?;
+        }
+
+        let indexed_count = self
+            .inputs
+            .iter()
+            .filter(|input| input.indexed.unwrap_or(false))
+            .count();
+        if topics.len() < indexed_count {
+            return Err(AbiError::InsufficientTopics {
+                expected: indexed_count,
+                found: topics.len(),
+            });
         }
 
         let mut topics_values = VecDeque::from(topics.to_vec());
 
-        let mut data_values = VecDeque::from(Value::decode_from_slice(
+        let mut data_values = VecDeque::from(Value::decode_from_slice_with_options(
             data,
             &self
                 .inputs
@@ -71,6 +161,7 @@ impl Event {
                 .filter(|input| !input.indexed.unwrap_or(false))
                 .map(|input| input.type_.clone())
                 .collect::<Vec<_>>(),
+            opts,
         )?);
 
         let mut decoded = vec![];
@@ -78,35 +169,43 @@ impl Event {
             let decoded_value = if input.indexed.unwrap_or(false) {
                 let val = topics_values
                     .pop_front()
-                    .ok_or_else(|| anyhow!("insufficient topics entries"))?;
+                    .expect("topics count checked above");
 
                 if Self::is_encoded_to_hash(&input.type_) {
                     Ok(Value::Hash(val))
-                } else if input.type_ == Type::U32
-                    || input.type_ == Type::Bool
-                    || input.type_ == Type::Field
-                {
+                } else if let Some(words) = Self::topic_words(&input.type_) {
                     // decode value from topics entry, using the input type
                     //  If the input type is hash or address, take the value directly.
-                    //  If the input type is u32, bool, field, take the last value (big-endian).
+                    //  If the input type is an integer of at most 64 bits, bool, field or
+                    //  enum, take the last words (big-endian).
 
-                    Value::decode_from_slice(
-                        &[val.0.get(3).unwrap().clone()],
-                        &[input.type_.clone()],
+                    Value::decode_from_slice_with_options(
+                        &val.0[4 - words..],
+                        std::slice::from_ref(&input.type_),
+                        opts,
                     )?
-                    .first()
-                    .ok_or_else(|| anyhow!("no value decoded from topics entry"))
-                    .map(Clone::clone)
+                    .pop()
+                    .ok_or(AbiError::UnexpectedEof {
+                        ty: input.type_.clone(),
+                        offset: 0,
+                    })
                 } else {
-                    Value::decode_from_slice(&val.0, &[input.type_.clone()])?
-                        .first()
-                        .ok_or_else(|| anyhow!("no value decoded from topics entry"))
-                        .map(Clone::clone)
+                    Value::decode_from_slice_with_options(
+                        &val.0,
+                        std::slice::from_ref(&input.type_),
+                        opts,
+                    )?
+                    .pop()
+                    .ok_or(AbiError::UnexpectedEof {
+                        ty: input.type_.clone(),
+                        offset: 0,
+                    })
                 }
             } else {
-                data_values
-                    .pop_front()
-                    .ok_or_else(|| anyhow!("insufficient data values"))
+                data_values.pop_front().ok_or(AbiError::UnexpectedEof {
+                    ty: input.type_.clone(),
+                    offset: data.len(),
+                })
             };
 
             decoded.push((input, decoded_value?));
@@ -115,23 +214,152 @@ impl Event {
         Ok(DecodedParams::from(decoded))
     }
 
+    /// Creates a topics filter for subscribing to this event's logs.
+    pub fn filter(&self) -> TopicFilter<'_> {
+        TopicFilter::new(self)
+    }
+
+    /// Encode event params into a log's topics and data, as emitted by the VM.
+    ///
+    /// Non-anonymous events get the event topic as first topic. Indexed params
+    /// follow as topics, all others are encoded into the data.
+    pub fn encode_data(&self, values: &[Value]) -> Result<(Vec<FixedArray4>, Vec<u64>)> {
+        check_params(&self.inputs, values)?;
+
+        let mut topics = vec![];
+        if !self.anonymous {
+            topics.push(self.topic());
+        }
+
+        let mut data_values = vec![];
+        for (input, value) in self.inputs.iter().zip(values) {
+            if input.indexed.unwrap_or(false) {
+                topics.push(value.encode_topic()?);
+            } else {
+                data_values.push(value.clone());
+            }
+        }
+
+        Ok((topics, Value::encode(&data_values)?))
+    }
+
+    /// Returns the number of words a value packed right-aligned into a topic takes.
+    fn topic_words(ty: &Type) -> Option<usize> {
+        match ty {
+            Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::I32
+            | Type::Bool
+            | Type::Field
+            | Type::Enum { .. } => Some(1),
+            Type::U64 | Type::I64 => Some(2),
+            _ => None,
+        }
+    }
+
     fn is_encoded_to_hash(ty: &Type) -> bool {
         matches!(
             ty,
             Type::FixedArray(_, _)
                 | Type::U256
+                | Type::FixedBytes(_)
                 | Type::Array(_)
                 | Type::Fields
                 | Type::String
                 | Type::Tuple(_)
+                | Type::Struct { .. }
         )
     }
 }
 
+/// Builds the topics filter of an event subscription.
+///
+/// Each indexed param can be matched against a value; params left unset match
+/// any value.
+///
+/// ```no_run
+/// # use ola_lang_abi::{Event, Value};
+/// # fn f(evt: &Event) -> Result<(), ola_lang_abi::AbiError> {
+/// let topics = evt
+///     .filter()
+///     .with_name("memo", Value::String("hello".to_string()))?
+///     .build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopicFilter<'a> {
+    event: &'a Event,
+    /// Expected topics entry per event input, set for indexed inputs only.
+    topics: Vec<Option<FixedArray4>>,
+}
+
+impl<'a> TopicFilter<'a> {
+    fn new(event: &'a Event) -> Self {
+        TopicFilter {
+            event,
+            topics: vec![None; event.inputs.len()],
+        }
+    }
+
+    /// Matches the indexed param with the given name against `value`.
+    pub fn with_name(self, name: &str, value: Value) -> Result<Self> {
+        let index = self
+            .event
+            .inputs
+            .iter()
+            .position(|input| input.name == name)
+            .ok_or_else(|| AbiError::UnknownParam(name.to_string()))?;
+
+        self.with_index(index, value)
+    }
+
+    /// Matches the indexed param at the given position in the event inputs against `value`.
+    pub fn with_index(mut self, index: usize, value: Value) -> Result<Self> {
+        let path = format!("inputs[{}]", index);
+
+        let input = self
+            .event
+            .inputs
+            .get(index)
+            .ok_or_else(|| AbiError::UnknownParam(path.clone()))?;
+
+        if !input.indexed.unwrap_or(false) {
+            return Err(AbiError::NotIndexed(path));
+        }
+
+        value.type_check(&input.type_, &path)?;
+
+        self.topics[index] = Some(value.encode_topic()?);
+
+        Ok(self)
+    }
+
+    /// Returns the topics filter, starting with the event topic for non-anonymous events.
+    pub fn build(&self) -> Vec<Option<FixedArray4>> {
+        let mut topics = vec![];
+        if !self.event.anonymous {
+            topics.push(Some(self.event.topic()));
+        }
+
+        topics.extend(
+            self.event
+                .inputs
+                .iter()
+                .zip(&self.topics)
+                .filter(|(input, _)| input.indexed.unwrap_or(false))
+                .map(|(_, topic)| *topic),
+        );
+
+        topics
+    }
+}
+
 #[cfg(test)]
 mod test {
 
-    use crate::{Abi, DecodedParams, Type};
+    use crate::{Abi, DecodedParams, FixedArray8, Type};
 
     use super::*;
 
@@ -145,11 +373,13 @@ mod test {
                     name: "x".to_string(),
                     type_: Type::U32,
                     indexed: Some(true),
+                    internal_type: None,
                 },
                 Param {
                     name: "y".to_string(),
                     type_: Type::String,
                     indexed: Some(true),
+                    internal_type: None,
                 },
             ],
             anonymous: false,
@@ -177,12 +407,58 @@ mod test {
         assert_eq!(evt.signature(), "Approve(u32,string)");
     }
 
+    #[test]
+    fn test_from_signature() {
+        let evt = Event::from_signature("BookCreated(u32,string,(address,u32[])[])")
+            .expect("from_signature failed");
+
+        assert_eq!(
+            evt,
+            Event {
+                name: "BookCreated".to_string(),
+                inputs: vec![
+                    Param {
+                        name: "".to_string(),
+                        type_: Type::U32,
+                        indexed: None,
+                        internal_type: None,
+                    },
+                    Param {
+                        name: "".to_string(),
+                        type_: Type::String,
+                        indexed: None,
+                        internal_type: None,
+                    },
+                    Param {
+                        name: "".to_string(),
+                        type_: Type::Array(Box::new(Type::Tuple(vec![
+                            ("".to_string(), Type::Address),
+                            ("".to_string(), Type::Array(Box::new(Type::U32))),
+                        ]))),
+                        indexed: None,
+                        internal_type: None,
+                    },
+                ],
+                anonymous: false,
+            }
+        );
+        assert_eq!(evt.signature(), "BookCreated(u32,string,(address,u32[])[])");
+
+        assert_eq!(
+            Event::from_signature("BookCreated(u32").unwrap_err(),
+            AbiError::InvalidSignature("BookCreated(u32".to_string())
+        );
+    }
+
     #[test]
     fn test_topic() {
         let evt = test_event();
         assert_eq!(
             evt.topic(),
-            FixedArray4::from("0xF9C165D12ACC9776822FF3684D676F567781B3609185E4A01ED1EA5138EAF215")
+            FixedArray4::from_hex_exact(
+                "0xF9C165D12ACC9776822FF3684D676F567781B3609185E4A01ED1EA5138EAF215"
+            )
+            .unwrap()
         );
     }
 
@@ -205,26 +481,31 @@ mod test {
             name: "x".to_string(),
             type_: Type::U32,
             indexed: None,
+            internal_type: None,
         };
         let y = Param {
             name: "y".to_string(),
             type_: Type::U32,
             indexed: Some(true),
+            internal_type: None,
         };
         let x1 = Param {
             name: "x1".to_string(),
             type_: Type::U32,
             indexed: None,
+            internal_type: None,
         };
         let y1 = Param {
             name: "y1".to_string(),
             type_: Type::U32,
             indexed: Some(true),
+            internal_type: None,
         };
         let s = Param {
             name: "s".to_string(),
             type_: Type::String,
             indexed: None,
+            internal_type: None,
         };
 
         let evt = Event {
@@ -236,8 +517,20 @@ mod test {
         let abi = Abi {
             functions: vec![],
             events: vec![evt],
+            errors: vec![],
+            constructor: None,
+            fallback: false,
+            receive: false,
         };
 
+        assert_eq!(
+            abi.index()
+                .decode_log_from_slice(&topics, &data)
+                .expect("decode_log_from_slice failed"),
+            abi.decode_log_from_slice(&topics, &data)
+                .expect("decode_log_from_slice failed")
+        );
+
         assert_eq!(
             abi.decode_log_from_slice(&topics, &data)
                 .expect("decode_log_from_slice failed"),
@@ -253,4 +546,428 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_decode_hashed_topics() {
+        let topics = vec![
+            FixedArray4([
+                17419482220628764836,
+                8907597850167663412,
+                18168554241148399331,
+                1999527075627474063,
+            ]),
+            FixedArray4([
+                11527978148837848074,
+                4289531113394413132,
+                6293787391853044962,
+                1944663377060687444,
+            ]),
+            FixedArray4([
+                16280171138638196442,
+                12304378830758561978,
+                10550751364702624917,
+                2046214134348436624,
+            ]),
+        ];
+
+        let data = vec![5, 104, 101, 108, 108, 111];
+
+        let evt = Event {
+            name: "BookPriced".to_string(),
+            inputs: vec![
+                Param {
+                    name: "price".to_string(),
+                    type_: Type::U256,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "ids".to_string(),
+                    type_: Type::Array(Box::new(Type::U32)),
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "note".to_string(),
+                    type_: Type::String,
+                    indexed: None,
+                    internal_type: None,
+                },
+            ],
+            anonymous: false,
+        };
+
+        let price = Value::U256(FixedArray8::from(1_000_000u64));
+        let ids = Value::Array(vec![Value::U32(1), Value::U32(2), Value::U32(3)], Type::U32);
+
+        assert_eq!(topics[1], price.encode_topic().unwrap());
+        assert_eq!(topics[2], ids.encode_topic().unwrap());
+
+        let decoded = evt.decode_data_from_slice(&topics, &data).unwrap();
+        assert_eq!(decoded[0].value, Value::Hash(topics[1]));
+        assert_eq!(decoded[1].value, Value::Hash(topics[2]));
+        assert_eq!(decoded[2].value, Value::String("hello".to_string()));
+    }
+
+    #[test]
+    fn test_encode_data() {
+        let evt = Event {
+            name: "BookCreated".to_string(),
+            inputs: vec![
+                Param {
+                    name: "id".to_string(),
+                    type_: Type::U32,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "name".to_string(),
+                    type_: Type::String,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "author".to_string(),
+                    type_: Type::String,
+                    indexed: None,
+                    internal_type: None,
+                },
+            ],
+            anonymous: false,
+        };
+
+        let (topics, data) = evt
+            .encode_data(&[
+                Value::U32(10),
+                Value::String("world".to_string()),
+                Value::String("hello".to_string()),
+            ])
+            .expect("encode_data failed");
+
+        assert_eq!(
+            topics,
+            vec![
+                FixedArray4([
+                    876009939773297099,
+                    9423535973325601276,
+                    68930750687700470,
+                    16776232995860792718,
+                ]),
+                FixedArray4([0, 0, 0, 10]),
+                FixedArray4([
+                    1298737262017568572,
+                    12445360621592034485,
+                    13004999764278192581,
+                    3441866816748036873,
+                ]),
+            ]
+        );
+        assert_eq!(data, vec![5, 104, 101, 108, 108, 111]);
+
+        let decoded = evt
+            .decode_data_from_slice(&topics, &data)
+            .expect("decode_data_from_slice failed");
+        assert_eq!(decoded[0].value, Value::U32(10));
+        assert_eq!(decoded[1].value, Value::Hash(topics[2]));
+        assert_eq!(decoded[2].value, Value::String("hello".to_string()));
+
+        assert_eq!(
+            evt.encode_data(&[Value::U32(10)]).unwrap_err(),
+            AbiError::LengthMismatch {
+                path: "inputs".to_string(),
+                expected: 3,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_encode_data_signed() {
+        let evt = Event {
+            name: "Moved".to_string(),
+            inputs: vec![
+                Param {
+                    name: "dx".to_string(),
+                    type_: Type::I32,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "dy".to_string(),
+                    type_: Type::I64,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+            ],
+            anonymous: true,
+        };
+
+        let (topics, data) = evt
+            .encode_data(&[Value::I32(-3), Value::I64(-4)])
+            .expect("encode_data failed");
+
+        assert_eq!(
+            topics,
+            vec![
+                FixedArray4([0, 0, 0, 0xfffffffd]),
+                FixedArray4([0, 0, 0xffffffff, 0xfffffffc]),
+            ]
+        );
+
+        let decoded = evt
+            .decode_data_from_slice(&topics, &data)
+            .expect("decode_data_from_slice failed");
+        assert_eq!(decoded[0].value, Value::I32(-3));
+        assert_eq!(decoded[1].value, Value::I64(-4));
+    }
+
+    #[test]
+    fn test_encode_data_sized() {
+        let evt = Event {
+            name: "Stamped".to_string(),
+            inputs: vec![
+                Param {
+                    name: "at".to_string(),
+                    type_: Type::U64,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "kind".to_string(),
+                    type_: Type::U8,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "tag".to_string(),
+                    type_: Type::String,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+            ],
+            anonymous: true,
+        };
+
+        let tag = Value::String("ab".to_string());
+        let (topics, data) = evt
+            .encode_data(&[Value::U64(1 << 40 | 5), Value::U8(7), tag.clone()])
+            .expect("encode_data failed");
+
+        assert_eq!(
+            topics,
+            vec![
+                FixedArray4([0, 0, 1 << 8, 5]),
+                FixedArray4([0, 0, 0, 7]),
+                tag.encode_topic().unwrap(),
+            ]
+        );
+
+        let decoded = evt
+            .decode_data_from_slice(&topics, &data)
+            .expect("decode_data_from_slice failed");
+        assert_eq!(decoded[0].value, Value::U64(1 << 40 | 5));
+        assert_eq!(decoded[1].value, Value::U8(7));
+        assert_eq!(decoded[2].value, Value::Hash(tag.encode_topic().unwrap()));
+
+        let mut topics = topics;
+        topics[1] = FixedArray4([0, 0, 0, 1 << 8]);
+        assert!(evt.decode_data_from_slice(&topics, &data).is_ok());
+        assert_eq!(
+            evt.decode_data_from_slice_with_options(&topics, &data, &DecodeOptions::strict())
+                .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::U8,
+                offset: 0,
+                word: 1 << 8
+            }
+        );
+    }
+
+    #[test]
+    fn test_encode_data_hashed() {
+        let point = Type::Tuple(vec![
+            ("x".to_string(), Type::U32),
+            ("y".to_string(), Type::U32),
+        ]);
+        let evt = Event {
+            name: "Moved".to_string(),
+            inputs: vec![
+                Param {
+                    name: "path".to_string(),
+                    type_: Type::Array(Box::new(point.clone())),
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "to".to_string(),
+                    type_: point.clone(),
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "steps".to_string(),
+                    type_: Type::U32,
+                    indexed: None,
+                    internal_type: None,
+                },
+            ],
+            anonymous: false,
+        };
+
+        let p = |x, y| {
+            Value::Tuple(vec![
+                ("x".to_string(), Value::U32(x)),
+                ("y".to_string(), Value::U32(y)),
+            ])
+        };
+        let path = Value::Array(vec![p(0, 0), p(1, 2)], point);
+        let to = p(3, 4);
+
+        let (topics, data) = evt
+            .encode_data(&[path.clone(), to.clone(), Value::U32(2)])
+            .expect("encode_data failed");
+
+        assert_eq!(
+            topics,
+            vec![
+                evt.topic(),
+                path.encode_topic().unwrap(),
+                to.encode_topic().unwrap(),
+            ]
+        );
+        assert_eq!(data, vec![2]);
+
+        let decoded = evt
+            .decode_data_from_slice(&topics, &data)
+            .expect("decode_data_from_slice failed");
+        assert_eq!(decoded[0].value, Value::Hash(path.encode_topic().unwrap()));
+        assert_eq!(decoded[1].value, Value::Hash(to.encode_topic().unwrap()));
+        assert_eq!(decoded[2].value, Value::U32(2));
+    }
+
+    #[test]
+    fn test_topic_filter() {
+        let evt = Event {
+            name: "Transfer".to_string(),
+            inputs: vec![
+                Param {
+                    name: "from".to_string(),
+                    type_: Type::Address,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "amount".to_string(),
+                    type_: Type::U32,
+                    indexed: None,
+                    internal_type: None,
+                },
+                Param {
+                    name: "memo".to_string(),
+                    type_: Type::String,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+            ],
+            anonymous: false,
+        };
+
+        let memo = Value::String("hello".to_string());
+        let topics = evt
+            .filter()
+            .with_name("memo", memo.clone())
+            .expect("with_name failed")
+            .build();
+        assert_eq!(
+            topics,
+            vec![Some(evt.topic()), None, Some(memo.encode_topic().unwrap())]
+        );
+
+        let from = FixedArray4([1, 2, 3, 4]);
+        let topics = evt
+            .filter()
+            .with_index(0, Value::Address(from))
+            .expect("with_index failed")
+            .build();
+        assert_eq!(topics, vec![Some(evt.topic()), Some(from), None]);
+
+        assert_eq!(
+            evt.filter().with_name("amount", Value::U32(1)).unwrap_err(),
+            AbiError::NotIndexed("inputs[1]".to_string())
+        );
+        assert_eq!(
+            evt.filter().with_name("to", Value::U32(1)).unwrap_err(),
+            AbiError::UnknownParam("to".to_string())
+        );
+        assert_eq!(
+            evt.filter().with_name("memo", Value::U32(1)).unwrap_err(),
+            AbiError::TypeMismatch {
+                path: "inputs[2]".to_string(),
+                expected: Type::String,
+                found: Type::U32
+            }
+        );
+    }
+
+    #[test]
+    fn test_topic_filter_hashed() {
+        let owner = Type::Tuple(vec![
+            ("id".to_string(), Type::U32),
+            ("name".to_string(), Type::String),
+        ]);
+        let evt = Event {
+            name: "Sold".to_string(),
+            inputs: vec![
+                Param {
+                    name: "price".to_string(),
+                    type_: Type::U256,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Param {
+                    name: "owner".to_string(),
+                    type_: owner,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+            ],
+            anonymous: false,
+        };
+
+        let price = Value::U256(FixedArray8::from(1_000_000u64));
+        let owner = Value::Tuple(vec![
+            ("id".to_string(), Value::U32(7)),
+            ("name".to_string(), Value::String("ola".to_string())),
+        ]);
+
+        let topics = evt
+            .filter()
+            .with_name("price", price.clone())
+            .expect("with_name failed")
+            .with_index(1, owner.clone())
+            .expect("with_index failed")
+            .build();
+        assert_eq!(
+            topics,
+            vec![
+                Some(evt.topic()),
+                Some(FixedArray4([
+                    11527978148837848074,
+                    4289531113394413132,
+                    6293787391853044962,
+                    1944663377060687444,
+                ])),
+                Some(FixedArray4([
+                    1151567286792565911,
+                    4758353786661641062,
+                    9144596007568226978,
+                    15322320578923640908,
+                ])),
+            ]
+        );
+
+        // the filter matches the log emitted for the same values.
+        let (log, _) = evt.encode_data(&[price, owner]).unwrap();
+        assert_eq!(topics, log.into_iter().map(Some).collect::<Vec<_>>());
+    }
 }
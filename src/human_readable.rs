@@ -0,0 +1,388 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, multispace0, multispace1},
+    combinator::{all_consuming, opt, verify},
+    multi::{many0, separated_list0},
+    sequence::{delimited, pair, preceded, terminated},
+    IResult,
+};
+
+use crate::{
+    abi::{Abi, Constructor, Function, StateMutability},
+    error::{AbiError, Result},
+    params::{parse_identifier, parse_inline_type},
+    Error, Event, Param,
+};
+
+impl Abi {
+    /// Parses an ABI from human-readable declarations.
+    ///
+    /// Each declaration is a function, event, error, constructor, fallback or
+    /// receive, written as in Ola source, e.g.:
+    ///
+    /// ```
+    /// use ola_lang_abi::Abi;
+    ///
+    /// let abi = Abi::parse_human_readable(&[
+    ///     "function vote(u32 proposal) returns (bool)",
+    ///     "function books() view returns ((u32 id, string name)[])",
+    ///     "event Transfer(address indexed from, address indexed to, u256 amount)",
+    ///     "error InsufficientBalance(u32 available, u32 required)",
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(abi.functions[1].signature(), "books()");
+    /// ```
+    pub fn parse_human_readable(declarations: &[&str]) -> Result<Abi> {
+        let mut abi = Abi {
+            functions: vec![],
+            events: vec![],
+            errors: vec![],
+            constructor: None,
+            fallback: false,
+            receive: false,
+        };
+
+        for declaration in declarations {
+            let invalid = || AbiError::InvalidDeclaration(declaration.to_string());
+
+            let (_, parsed) =
+                all_consuming(delimited(multispace0, parse_declaration, multispace0))(declaration)
+                    .map_err(|_| invalid())?;
+
+            match parsed {
+                Declaration::Function(f) => abi.functions.push(f),
+                Declaration::Event(e) => abi.events.push(e),
+                Declaration::Error(e) => abi.errors.push(e),
+                Declaration::Constructor(c) => {
+                    if abi.constructor.is_some() {
+                        return Err(invalid());
+                    }
+                    abi.constructor = Some(c);
+                }
+                Declaration::Fallback => abi.fallback = true,
+                Declaration::Receive => abi.receive = true,
+            }
+        }
+
+        abi.validate()?;
+
+        Ok(abi)
+    }
+}
+
+/// Parses a signature, e.g. `vote(u32,(address,string)[])`, into a name and
+/// unnamed params.
+pub(crate) fn parse_signature(signature: &str) -> Result<(String, Vec<Param>)> {
+    let (_, (name, inputs)) = all_consuming(pair(parse_identifier, parse_params(false)))(signature)
+        .map_err(|_: nom::Err<nom::error::Error<&str>>| {
+            AbiError::InvalidSignature(signature.to_string())
+        })?;
+
+    Ok((name.to_string(), inputs))
+}
+
+#[derive(Clone)]
+enum Declaration {
+    Function(Function),
+    Event(Event),
+    Error(Error),
+    Constructor(Constructor),
+    Fallback,
+    Receive,
+}
+
+fn parse_declaration(input: &str) -> IResult<&str, Declaration> {
+    alt((
+        parse_function,
+        parse_event,
+        parse_error,
+        parse_constructor,
+        parse_special("fallback", Declaration::Fallback),
+        parse_special("receive", Declaration::Receive),
+    ))(input)
+}
+
+fn parse_function(input: &str) -> IResult<&str, Declaration> {
+    let (i, name) = preceded(pair(tag("function"), multispace1), parse_identifier)(input)?;
+    let (i, inputs) = preceded(multispace0, parse_params(false))(i)?;
+    let (i, state_mutability) = parse_modifiers(i)?;
+    let (i, outputs) = opt(preceded(
+        delimited(multispace0, tag("returns"), multispace0),
+        parse_params(false),
+    ))(i)?;
+
+    Ok((
+        i,
+        Declaration::Function(Function {
+            name: name.to_string(),
+            inputs,
+            outputs: outputs.unwrap_or_default(),
+            state_mutability,
+            constant: None,
+            payable: None,
+        }),
+    ))
+}
+
+fn parse_event(input: &str) -> IResult<&str, Declaration> {
+    let (i, name) = preceded(pair(tag("event"), multispace1), parse_identifier)(input)?;
+    let (i, inputs) = preceded(multispace0, parse_params(true))(i)?;
+    let (i, anonymous) = opt(preceded(multispace1, tag("anonymous")))(i)?;
+
+    Ok((
+        i,
+        Declaration::Event(Event {
+            name: name.to_string(),
+            inputs,
+            anonymous: anonymous.is_some(),
+        }),
+    ))
+}
+
+fn parse_error(input: &str) -> IResult<&str, Declaration> {
+    let (i, name) = preceded(pair(tag("error"), multispace1), parse_identifier)(input)?;
+    let (i, inputs) = preceded(multispace0, parse_params(false))(i)?;
+
+    Ok((
+        i,
+        Declaration::Error(Error {
+            name: name.to_string(),
+            inputs,
+        }),
+    ))
+}
+
+fn parse_constructor(input: &str) -> IResult<&str, Declaration> {
+    let (i, inputs) = preceded(pair(tag("constructor"), multispace0), parse_params(false))(input)?;
+    let (i, _) = parse_modifiers(i)?;
+
+    Ok((i, Declaration::Constructor(Constructor { inputs })))
+}
+
+fn parse_special(
+    keyword: &'static str,
+    declaration: Declaration,
+) -> impl Fn(&str) -> IResult<&str, Declaration> {
+    move |input: &str| {
+        let (i, _) = terminated(
+            tag(keyword),
+            delimited(multispace0, pair(char('('), multispace0), char(')')),
+        )(input)?;
+        let (i, _) = parse_modifiers(i)?;
+
+        Ok((i, declaration.clone()))
+    }
+}
+
+/// Parses the visibility and state mutability keywords following a param list.
+fn parse_modifiers(input: &str) -> IResult<&str, StateMutability> {
+    let modifier = verify(parse_identifier, |word: &str| {
+        matches!(
+            word,
+            "pure" | "view" | "constant" | "payable" | "nonpayable" | "external" | "public"
+        )
+    });
+
+    let (i, words) = many0(preceded(multispace1, modifier))(input)?;
+
+    let state_mutability =
+        words
+            .iter()
+            .fold(StateMutability::NonPayable, |acc, word| match *word {
+                "pure" => StateMutability::Pure,
+                "view" | "constant" => StateMutability::View,
+                "payable" => StateMutability::Payable,
+                _ => acc,
+            });
+
+    Ok((i, state_mutability))
+}
+
+/// Parses a parenthesized param list, e.g. `(address indexed from, u32)`.
+///
+/// Only event params may be `indexed`, and are then marked as indexed or not.
+fn parse_params(event: bool) -> impl Fn(&str) -> IResult<&str, Vec<Param>> {
+    move |input: &str| {
+        delimited(
+            pair(char('('), multispace0),
+            separated_list0(
+                delimited(multispace0, char(','), multispace0),
+                parse_param(event),
+            ),
+            pair(multispace0, char(')')),
+        )(input)
+    }
+}
+
+fn parse_param(event: bool) -> impl Fn(&str) -> IResult<&str, Param> {
+    move |input: &str| {
+        let (i, type_) = parse_inline_type(input)?;
+        let (i, indexed) = opt(preceded(
+            multispace1,
+            verify(parse_identifier, |word: &str| event && word == "indexed"),
+        ))(i)?;
+        let (i, name) = opt(preceded(
+            multispace1,
+            verify(parse_identifier, |word: &str| word != "indexed"),
+        ))(i)?;
+
+        Ok((
+            i,
+            Param {
+                name: name.unwrap_or_default().to_string(),
+                type_,
+                indexed: event.then_some(indexed.is_some()),
+                internal_type: None,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::Type;
+
+    #[test]
+    fn parse_human_readable() {
+        let abi = Abi::parse_human_readable(&[
+            "function vote(u32 proposal) returns (bool)",
+            "function books(address owner) view returns ((u32 id, string name)[] books, u32)",
+            "event Transfer(address indexed from, address indexed to, u256 amount)",
+            "event Ping() anonymous",
+            "error InsufficientBalance(u32 available, u32 required)",
+            "constructor(string[] names) payable",
+            "fallback() external",
+            "receive() external payable",
+        ])
+        .expect("parse_human_readable failed");
+
+        let param = |name: &str, type_: Type, indexed: Option<bool>| Param {
+            name: name.to_string(),
+            type_,
+            indexed,
+            internal_type: None,
+        };
+
+        assert_eq!(
+            abi.functions,
+            vec![
+                Function {
+                    name: "vote".to_string(),
+                    inputs: vec![param("proposal", Type::U32, None)],
+                    outputs: vec![param("", Type::Bool, None)],
+                    state_mutability: StateMutability::NonPayable,
+                    constant: None,
+                    payable: None,
+                },
+                Function {
+                    name: "books".to_string(),
+                    inputs: vec![param("owner", Type::Address, None)],
+                    outputs: vec![
+                        param(
+                            "books",
+                            Type::Array(Box::new(Type::Tuple(vec![
+                                ("id".to_string(), Type::U32),
+                                ("name".to_string(), Type::String),
+                            ]))),
+                            None
+                        ),
+                        param("", Type::U32, None),
+                    ],
+                    state_mutability: StateMutability::View,
+                    constant: None,
+                    payable: None,
+                },
+            ]
+        );
+        assert_eq!(
+            abi.events,
+            vec![
+                Event {
+                    name: "Transfer".to_string(),
+                    inputs: vec![
+                        param("from", Type::Address, Some(true)),
+                        param("to", Type::Address, Some(true)),
+                        param("amount", Type::U256, Some(false)),
+                    ],
+                    anonymous: false,
+                },
+                Event {
+                    name: "Ping".to_string(),
+                    inputs: vec![],
+                    anonymous: true,
+                },
+            ]
+        );
+        assert_eq!(
+            abi.errors,
+            vec![Error {
+                name: "InsufficientBalance".to_string(),
+                inputs: vec![
+                    param("available", Type::U32, None),
+                    param("required", Type::U32, None),
+                ],
+            }]
+        );
+        assert_eq!(
+            abi.constructor,
+            Some(Constructor {
+                inputs: vec![param("names", Type::Array(Box::new(Type::String)), None)],
+            })
+        );
+        assert!(abi.fallback);
+        assert!(abi.receive);
+        assert_eq!(abi.functions[1].signature(), "books(address)");
+    }
+
+    #[test]
+    fn parse_human_readable_matches_json() {
+        let json: Abi = serde_json::from_value(serde_json::json!([
+            {
+                "type": "function",
+                "name": "f",
+                "inputs": [
+                    {"name": "x", "type": "tuple[2]", "components": [
+                        {"name": "a", "type": "u32"},
+                        {"name": "b", "type": "hash[]"}
+                    ]},
+                    {"name": "", "type": "bytes4"}
+                ],
+                "outputs": [],
+                "stateMutability": "pure"
+            }
+        ]))
+        .unwrap();
+
+        let human = Abi::parse_human_readable(&["function f((u32 a, hash[] b)[2] x, bytes4) pure"])
+            .unwrap();
+
+        assert_eq!(human, json);
+    }
+
+    #[test]
+    fn parse_human_readable_errors() {
+        for declaration in [
+            "function f(u32 indexed x)",
+            "function f(u33)",
+            "function f(u32) returns",
+            "function (u32)",
+            "event E(u32 indexed indexed)",
+            "struct S(u32)",
+        ] {
+            assert_eq!(
+                Abi::parse_human_readable(&[declaration]).unwrap_err(),
+                AbiError::InvalidDeclaration(declaration.to_string())
+            );
+        }
+
+        assert_eq!(
+            Abi::parse_human_readable(&["constructor()", "constructor(u32)"]).unwrap_err(),
+            AbiError::InvalidDeclaration("constructor(u32)".to_string())
+        );
+    }
+}
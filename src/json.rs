@@ -0,0 +1,558 @@
+use serde::{
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Serialize,
+};
+use serde_json::Value as Json;
+
+use crate::{
+    error::{AbiError, Result},
+    params::{DecodedParam, DecodedParams, Param},
+    types::Type,
+    values::{FixedArray4, FixedArray8, Value},
+};
+
+/// Serializes values to their canonical JSON representation:
+///
+/// | Type                      | JSON                                                    |
+/// |---------------------------|---------------------------------------------------------|
+/// | `u8`, `u16`, `u32`, `i32` | number                                                  |
+/// | `u64`, `i64`, `field`     | decimal string                                          |
+/// | `u256`, `address`, `hash` | `0x`-prefixed hex string of 64 digits                   |
+/// | `bytesN`                  | `0x`-prefixed hex string of `2 * N` digits              |
+/// | `bool`                    | boolean                                                 |
+/// | `string`                  | string                                                  |
+/// | `fields`                  | array of decimal strings                                |
+/// | `T[k]`, `T[]`             | array                                                   |
+/// | tuple, struct             | object keyed by field name, array if a field is unnamed |
+/// | enum                      | variant name                                            |
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Value::U8(i) => serializer.serialize_u8(*i),
+            Value::U16(i) => serializer.serialize_u16(*i),
+            Value::U32(i) => serializer.serialize_u64(*i),
+            Value::I32(i) => serializer.serialize_i32(*i),
+            Value::U64(i) | Value::Field(i) => serializer.serialize_str(&i.to_string()),
+            Value::I64(i) => serializer.serialize_str(&i.to_string()),
+            Value::U256(num) => serializer.serialize_str(&num.to_hex_string()),
+            Value::Address(v) | Value::Hash(v) => serializer.serialize_str(&v.to_hex_string()),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::FixedBytes(bytes) => serializer.serialize_str(&format!(
+                "0x{}",
+                bytes
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<String>()
+            )),
+            Value::String(s) | Value::Enum(s, _) => serializer.serialize_str(s),
+            Value::Fields(fields) => {
+                let mut seq = serializer.serialize_seq(Some(fields.len()))?;
+                for field in fields {
+                    seq.serialize_element(&field.to_string())?;
+                }
+                seq.end()
+            }
+            Value::FixedArray(values, _) | Value::Array(values, _) => values.serialize(serializer),
+            Value::Tuple(fields) => {
+                if fields.iter().any(|(name, _)| name.is_empty()) {
+                    let mut seq = serializer.serialize_seq(Some(fields.len()))?;
+                    for (_, value) in fields {
+                        seq.serialize_element(value)?;
+                    }
+                    seq.end()
+                } else {
+                    let mut map = serializer.serialize_map(Some(fields.len()))?;
+                    for (name, value) in fields {
+                        map.serialize_entry(name, value)?;
+                    }
+                    map.end()
+                }
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Builds a value of the given type from its JSON representation.
+    ///
+    /// Accepts the representation [`Value`] serializes to, and is lenient where
+    /// this is unambiguous: integers and fields may be given as numbers or decimal
+    /// strings, `u256` as a decimal string or a `0x`-prefixed hex string, and
+    /// tuples as arrays.
+    pub fn from_json(json: &Json, ty: &Type) -> Result<Self> {
+        Self::from_json_at(json, ty, "$")
+    }
+
+    fn from_json_at(json: &Json, ty: &Type, path: &str) -> Result<Self> {
+        let invalid = || AbiError::InvalidValue {
+            path: path.to_string(),
+            ty: Box::new(ty.clone()),
+            input: json.to_string(),
+        };
+
+        let value = match ty {
+            Type::U8 => Value::U8(
+                json_u64(json)
+                    .and_then(|i| i.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            Type::U16 => Value::U16(
+                json_u64(json)
+                    .and_then(|i| i.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            Type::U32 => Value::U32(
+                json_u64(json)
+                    .filter(|i| *i <= u32::MAX as u64)
+                    .ok_or_else(invalid)?,
+            ),
+            Type::U64 => Value::U64(json_u64(json).ok_or_else(invalid)?),
+            Type::I32 => Value::I32(
+                json_i64(json)
+                    .and_then(|i| i.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            Type::I64 => Value::I64(json_i64(json).ok_or_else(invalid)?),
+            Type::Field => Value::Field(json_field(json).ok_or_else(invalid)?),
+            Type::U256 => Value::U256(
+                match json {
+                    Json::String(s) if s.starts_with("0x") => s.parse::<FixedArray8>().ok(),
+                    Json::String(s) => FixedArray8::from_dec_str(s).ok(),
+                    Json::Number(n) => n.as_u64().map(FixedArray8::from),
+                    _ => None,
+                }
+                .ok_or_else(invalid)?,
+            ),
+            Type::Address => Value::Address(json_fixed_array4(json).ok_or_else(invalid)?),
+            Type::Hash => Value::Hash(json_fixed_array4(json).ok_or_else(invalid)?),
+            Type::Bool => Value::Bool(json.as_bool().ok_or_else(invalid)?),
+            Type::FixedBytes(size) => Value::FixedBytes(
+                json.as_str()
+                    .and_then(parse_hex)
+                    .filter(|bytes| bytes.len() == *size)
+                    .ok_or_else(invalid)?,
+            ),
+            Type::String => Value::String(json.as_str().ok_or_else(invalid)?.to_string()),
+            Type::Fields => {
+                let fields = json.as_array().ok_or_else(invalid)?;
+                Value::Fields(
+                    fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, field)| {
+                            json_field(field).ok_or_else(|| AbiError::InvalidValue {
+                                path: format!("{}[{}]", path, i),
+                                ty: Box::new(Type::Field),
+                                input: field.to_string(),
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                )
+            }
+            Type::FixedArray(elem_ty, size) => {
+                let values = json.as_array().ok_or_else(invalid)?;
+                if values.len() as u64 != *size {
+                    return Err(AbiError::LengthMismatch {
+                        path: path.to_string(),
+                        expected: *size as usize,
+                        found: values.len(),
+                    });
+                }
+
+                Value::FixedArray(
+                    Self::from_json_elements(values, elem_ty, path)?,
+                    *elem_ty.clone(),
+                )
+            }
+            Type::Array(elem_ty) => {
+                let values = json.as_array().ok_or_else(invalid)?;
+
+                Value::Array(
+                    Self::from_json_elements(values, elem_ty, path)?,
+                    *elem_ty.clone(),
+                )
+            }
+            Type::Tuple(fields) | Type::Struct { fields, .. } => {
+                let values = match json {
+                    Json::Array(values) => {
+                        if values.len() != fields.len() {
+                            return Err(AbiError::LengthMismatch {
+                                path: path.to_string(),
+                                expected: fields.len(),
+                                found: values.len(),
+                            });
+                        }
+                        values.iter().collect::<Vec<_>>()
+                    }
+                    Json::Object(map) => {
+                        if let Some(name) = map
+                            .keys()
+                            .find(|name| !fields.iter().any(|(field, _)| field == *name))
+                        {
+                            return Err(AbiError::UnknownParam(format!("{}.{}", path, name)));
+                        }
+
+                        // missing fields are reported as invalid null values.
+                        fields
+                            .iter()
+                            .map(|(name, _)| map.get(name).unwrap_or(&Json::Null))
+                            .collect()
+                    }
+                    _ => return Err(invalid()),
+                };
+
+                Value::Tuple(
+                    fields
+                        .iter()
+                        .zip(values)
+                        .enumerate()
+                        .map(|(i, ((name, ty), value))| {
+                            let field = if name.is_empty() {
+                                i.to_string()
+                            } else {
+                                name.clone()
+                            };
+                            let value =
+                                Self::from_json_at(value, ty, &format!("{}.{}", path, field))?;
+
+                            Ok((name.clone(), value))
+                        })
+                        .collect::<Result<_>>()?,
+                )
+            }
+            Type::Enum { variants, .. } => {
+                let variant = json
+                    .as_str()
+                    .filter(|variant| variants.iter().any(|v| v == variant))
+                    .ok_or_else(invalid)?;
+
+                Value::Enum(variant.to_string(), ty.clone())
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn from_json_elements(values: &[Json], ty: &Type, path: &str) -> Result<Vec<Self>> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Self::from_json_at(value, ty, &format!("{}[{}]", path, i)))
+            .collect()
+    }
+}
+
+fn json_u64(json: &Json) -> Option<u64> {
+    match json {
+        Json::Number(n) => n.as_u64(),
+        Json::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_i64(json: &Json) -> Option<i64> {
+    match json {
+        Json::Number(n) => n.as_i64(),
+        Json::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_field(json: &Json) -> Option<u64> {
+    json_u64(json).filter(|i| Value::field(*i).is_ok())
+}
+
+fn json_fixed_array4(json: &Json) -> Option<FixedArray4> {
+    json.as_str().and_then(|s| s.parse().ok())
+}
+
+/// Parses a `0x`-prefixed hex string into bytes.
+pub(crate) fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    let digits = s.strip_prefix("0x")?;
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Serializes a decoded param as its [`Param`] definition with a `value` field.
+impl Serialize for DecodedParam {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut entry = serde_json::to_value(&self.param).map_err(serde::ser::Error::custom)?;
+        if let Json::Object(map) = &mut entry {
+            map.insert(
+                "value".to_string(),
+                serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)?,
+            );
+        }
+
+        entry.serialize(serializer)
+    }
+}
+
+impl<'a> Deserialize<'a> for DecodedParam {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let mut entry = Json::deserialize(deserializer)?;
+        let value = entry
+            .as_object_mut()
+            .and_then(|map| map.remove("value"))
+            .ok_or_else(|| serde::de::Error::missing_field("value"))?;
+
+        let param: Param = serde_json::from_value(entry).map_err(serde::de::Error::custom)?;
+        let value = Value::from_json(&value, &param.type_).map_err(serde::de::Error::custom)?;
+
+        Ok(DecodedParam { param, value })
+    }
+}
+
+/// Serializes decoded params as an array of decoded params.
+impl Serialize for DecodedParams {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl<'a> Deserialize<'a> for DecodedParams {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let params: Vec<DecodedParam> = Deserialize::deserialize(deserializer)?;
+
+        Ok(DecodedParams::from(
+            params
+                .into_iter()
+                .map(|param| (param.param, param.value))
+                .collect::<Vec<_>>(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn color() -> Type {
+        Type::Enum {
+            name: "Color".to_string(),
+            variants: vec!["Red".to_string(), "Green".to_string()],
+        }
+    }
+
+    fn book() -> Type {
+        Type::Tuple(vec![
+            ("id".to_string(), Type::U64),
+            ("owner".to_string(), Type::Address),
+            ("tags".to_string(), Type::Array(Box::new(Type::String))),
+            ("color".to_string(), color()),
+        ])
+    }
+
+    #[test]
+    fn serialize_values() {
+        let values = vec![
+            Value::U8(1),
+            Value::U32(2),
+            Value::I32(-3),
+            Value::U64(u64::MAX),
+            Value::I64(-4),
+            Value::Field(5),
+            Value::U256(FixedArray8::from(6u64)),
+            Value::Hash(FixedArray4([0, 0, 0, 7])),
+            Value::FixedBytes(vec![0xab, 0x01]),
+            Value::Bool(true),
+            Value::Fields(vec![8, 9]),
+            Value::Tuple(vec![
+                ("".to_string(), Value::U32(10)),
+                ("".to_string(), Value::String("x".to_string())),
+            ]),
+            Value::Enum("Green".to_string(), color()),
+        ];
+
+        assert_eq!(
+            serde_json::to_value(&values).unwrap(),
+            json!([
+                1,
+                2,
+                -3,
+                "18446744073709551615",
+                "-4",
+                "5",
+                format!("0x{:064x}", 6),
+                format!("0x{:064x}", 7),
+                "0xab01",
+                true,
+                ["8", "9"],
+                [10, "x"],
+                "Green",
+            ])
+        );
+    }
+
+    #[test]
+    fn from_json_round_trip() {
+        let ty = Type::Array(Box::new(book()));
+        let value = Value::Array(
+            vec![Value::Tuple(vec![
+                ("id".to_string(), Value::U64(1)),
+                (
+                    "owner".to_string(),
+                    Value::Address(FixedArray4([1, 2, 3, 4])),
+                ),
+                (
+                    "tags".to_string(),
+                    Value::Array(vec![Value::String("a".to_string())], Type::String),
+                ),
+                ("color".to_string(), Value::Enum("Red".to_string(), color())),
+            ])],
+            book(),
+        );
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json[0]["id"], json!("1"));
+        assert_eq!(Value::from_json(&json, &ty).unwrap(), value);
+    }
+
+    #[test]
+    fn from_json_lenient() {
+        assert_eq!(
+            Value::from_json(&json!(12), &Type::U64).unwrap(),
+            Value::U64(12)
+        );
+        assert_eq!(
+            Value::from_json(&json!("12"), &Type::U256).unwrap(),
+            Value::U256(FixedArray8::from(12u64))
+        );
+        assert_eq!(
+            Value::from_json(
+                &json!([1, "a"]),
+                &Type::Tuple(vec![
+                    ("n".to_string(), Type::U32),
+                    ("s".to_string(), Type::String),
+                ])
+            )
+            .unwrap(),
+            Value::Tuple(vec![
+                ("n".to_string(), Value::U32(1)),
+                ("s".to_string(), Value::String("a".to_string())),
+            ])
+        );
+    }
+
+    #[test]
+    fn from_json_errors() {
+        let ty = Type::Array(Box::new(book()));
+        let invalid = |path: &str, ty: Type, input: &str| AbiError::InvalidValue {
+            path: path.to_string(),
+            ty: Box::new(ty),
+            input: input.to_string(),
+        };
+
+        assert_eq!(
+            Value::from_json(&json!(256), &Type::U8).unwrap_err(),
+            invalid("$", Type::U8, "256")
+        );
+        assert_eq!(
+            Value::from_json(&json!(u64::MAX), &Type::Field).unwrap_err(),
+            invalid("$", Type::Field, "18446744073709551615")
+        );
+        assert_eq!(
+            Value::from_json(&json!("0xab"), &Type::FixedBytes(2)).unwrap_err(),
+            invalid("$", Type::FixedBytes(2), "\"0xab\"")
+        );
+        assert_eq!(
+            Value::from_json(&json!([{"id": 1, "tags": [], "color": "Red"}]), &ty).unwrap_err(),
+            invalid("$[0].owner", Type::Address, "null")
+        );
+        assert_eq!(
+            Value::from_json(
+                &json!([{"id": 1, "owner": "0x1", "tags": [true], "color": "Red"}]),
+                &ty
+            )
+            .unwrap_err(),
+            invalid("$[0].tags[0]", Type::String, "true")
+        );
+        assert_eq!(
+            Value::from_json(
+                &json!([{"id": 1, "owner": "0x1", "tags": [], "color": "Blue"}]),
+                &ty
+            )
+            .unwrap_err(),
+            invalid("$[0].color", color(), "\"Blue\"")
+        );
+        assert_eq!(
+            Value::from_json(&json!([{"id": 1, "title": "x"}]), &ty).unwrap_err(),
+            AbiError::UnknownParam("$[0].title".to_string())
+        );
+        assert_eq!(
+            Value::from_json(&json!([1, 2]), &Type::FixedArray(Box::new(Type::U32), 3))
+                .unwrap_err(),
+            AbiError::LengthMismatch {
+                path: "$".to_string(),
+                expected: 3,
+                found: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn decoded_params_round_trip() {
+        let params = DecodedParams::from(vec![
+            (
+                Param {
+                    name: "book".to_string(),
+                    type_: book(),
+                    indexed: None,
+                    internal_type: None,
+                },
+                Value::Tuple(vec![
+                    ("id".to_string(), Value::U64(1)),
+                    (
+                        "owner".to_string(),
+                        Value::Address(FixedArray4([0, 0, 0, 1])),
+                    ),
+                    ("tags".to_string(), Value::Array(vec![], Type::String)),
+                    ("color".to_string(), Value::Enum("Red".to_string(), color())),
+                ]),
+            ),
+            (
+                Param {
+                    name: "ok".to_string(),
+                    type_: Type::Bool,
+                    indexed: Some(true),
+                    internal_type: None,
+                },
+                Value::Bool(false),
+            ),
+        ]);
+
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            json[1],
+            json!({"name": "ok", "type": "bool", "indexed": true, "value": false})
+        );
+        assert_eq!(
+            serde_json::from_value::<DecodedParams>(json).unwrap(),
+            params
+        );
+    }
+}
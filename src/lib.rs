@@ -1,12 +1,23 @@
 //! Ethereum Smart Contracts ABI (abstract binary interface) utility library.
 
+// `AbiError::TypeMismatch` holds both types by value, so that callers can match
+// on them, which makes the error larger than clippy's default threshold.
+#![allow(clippy::result_large_err)]
+
 mod abi;
+mod convert;
+mod error;
 mod event;
+mod human_readable;
+mod json;
 mod params;
 mod types;
+mod value_parser;
 mod values;
 
 pub use abi::*;
+pub use convert::AbiType;
+pub use error::{AbiError, FromDecError, FromHexError};
 pub use event::*;
 pub use params::*;
 pub use types::*;
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::HashMap, fmt, rc::Rc};
 
 use crate::{types::Type, Value};
 
@@ -18,6 +18,18 @@ impl From<(Param, Value)> for DecodedParam {
     }
 }
 
+/// Formats the param as `name: type = value`, with structs and enums labelled by
+/// their name and nested values laid out over several lines.
+impl fmt::Display for DecodedParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} = {:#}",
+            self.param.name, self.param.type_, self.value
+        )
+    }
+}
+
 /// ABI decoded values. Fast access by param index and name.
 ///
 /// This struct provides a way for accessing decoded param values by index and by name.
@@ -28,7 +40,7 @@ impl DecodedParams {
     /// Creates a reader.
     ///
     /// Parameters are indexed by name at reader creation.
-    pub fn reader(&self) -> DecodedParamsReader {
+    pub fn reader(&self) -> DecodedParamsReader<'_> {
         DecodedParamsReader::new(self)
     }
 }
@@ -47,6 +59,26 @@ impl From<Vec<(Param, Value)>> for DecodedParams {
     }
 }
 
+/// Formats the params one per line as `name: type = value`, naming unnamed
+/// params by their position.
+impl fmt::Display for DecodedParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, param) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            if param.param.name.is_empty() {
+                write!(f, "{}: {} = {:#}", i, param.param.type_, param.value)?;
+            } else {
+                write!(f, "{}", param)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Provides fast read access to decoded params by parameter index and name.
 pub struct DecodedParamsReader<'a> {
     /// Decoded params by parameter index.
@@ -78,41 +110,50 @@ pub struct Param {
     pub type_: Type,
     /// Whether it is an indexed parameter (events only).
     pub indexed: Option<bool>,
+    /// Source-level type emitted by the compiler, e.g. `struct Book` or `u32[]`.
+    pub internal_type: Option<String>,
 }
 
 impl Param {
     fn build_param_entry(&self) -> ParamEntry {
-        let tuple_params = match &self.type_ {
-            Type::Tuple(params) => Some(params.clone()),
-            Type::Array(ty) | Type::FixedArray(ty, _) => {
-                if let Type::Tuple(params) = ty.as_ref() {
-                    Some(params.clone())
-                } else {
-                    None
-                }
-            }
+        let components = match element_type(&self.type_) {
+            Type::Tuple(fields) | Type::Struct { fields, .. } => Some(
+                fields
+                    .iter()
+                    .map(|(name, ty)| {
+                        // keep components in line with the compiler output, which gives
+                        // every component an internal type when it gives one to the param.
+                        let internal_type = default_internal_type(ty)
+                            .or_else(|| self.internal_type.as_ref().map(|_| param_type_string(ty)));
+
+                        Param {
+                            name: name.clone(),
+                            type_: ty.clone(),
+                            indexed: None,
+                            internal_type,
+                        }
+                        .build_param_entry()
+                    })
+                    .collect(),
+            ),
             _ => None,
         };
 
-        let components = tuple_params.map(|params| {
-            params
-                .iter()
-                .map(|(name, ty)| {
-                    Param {
-                        name: name.clone(),
-                        type_: ty.clone(),
-                        indexed: None,
-                    }
-                    .build_param_entry()
-                })
-                .collect()
-        });
+        let variants = match element_type(&self.type_) {
+            Type::Enum { variants, .. } => Some(variants.clone()),
+            _ => None,
+        };
 
         ParamEntry {
             name: self.name.clone(),
             type_: param_type_string(&self.type_),
             indexed: self.indexed,
             components,
+            internal_type: self
+                .internal_type
+                .clone()
+                .or_else(|| default_internal_type(&self.type_)),
+            variants,
         }
     }
 }
@@ -133,23 +174,97 @@ impl<'a> Deserialize<'a> for Param {
     {
         let entry: ParamEntry = Deserialize::deserialize(deserializer)?;
 
-        let (_, ty) = parse_exact_type(Rc::new(entry.components), &entry.type_)
-            .map_err(|e| serde::de::Error::custom(e.to_string()))?;
+        let ty = entry_type(&entry).map_err(serde::de::Error::custom)?;
 
         Ok(Param {
             name: entry.name.to_string(),
             type_: ty,
             indexed: entry.indexed,
+            internal_type: entry.internal_type,
         })
     }
 }
 
+/// Returns the element type of a possibly nested array type, or the type itself.
+fn element_type(ty: &Type) -> &Type {
+    match ty {
+        Type::Array(ty) | Type::FixedArray(ty, _) => element_type(ty),
+        ty => ty,
+    }
+}
+
+/// Returns the internal type naming a struct or enum, or an array of them.
+fn default_internal_type(ty: &Type) -> Option<String> {
+    let (kind, name) = match element_type(ty) {
+        Type::Struct { name, .. } => ("struct", name),
+        Type::Enum { name, .. } => ("enum", name),
+        _ => return None,
+    };
+
+    // the array suffix, e.g. `[2][]`, follows the element type string.
+    let type_string = param_type_string(ty);
+    let suffix = &type_string[type_string.find('[').unwrap_or(type_string.len())..];
+
+    Some(format!("{} {}{}", kind, name, suffix))
+}
+
+/// Parses the type of an entry.
+///
+/// A tuple entry, or an array of tuples, whose `internalType` is `struct Name`
+/// is a struct. A `u32` entry, or an array of `u32`, that declares `variants` is
+/// an enum, named after its `internalType`, e.g. `enum Color`.
+fn entry_type(entry: &ParamEntry) -> Result<Type, String> {
+    let (_, ty) = parse_exact_type(Rc::new(entry.components.clone()), &entry.type_)
+        .map_err(|e| e.to_string())?;
+
+    let internal_type = entry.internal_type.as_deref().unwrap_or_default();
+    let name = internal_type
+        .trim_start_matches("struct ")
+        .trim_start_matches("enum ");
+    let name = name.split('[').next().unwrap_or_default();
+
+    fn map_element(ty: Type, f: &dyn Fn(Type) -> Result<Type, String>) -> Result<Type, String> {
+        match ty {
+            Type::Array(ty) => Ok(Type::Array(Box::new(map_element(*ty, f)?))),
+            Type::FixedArray(ty, size) => {
+                Ok(Type::FixedArray(Box::new(map_element(*ty, f)?), size))
+            }
+            ty => f(ty),
+        }
+    }
+
+    if let Some(variants) = &entry.variants {
+        return map_element(ty, &|ty| match ty {
+            Type::U32 => Ok(Type::Enum {
+                name: name.to_string(),
+                variants: variants.clone(),
+            }),
+            ty => Err(format!(
+                "enum {} must be declared as u32, found {}",
+                name, ty
+            )),
+        });
+    }
+
+    if internal_type.starts_with("struct ") {
+        return map_element(ty, &|ty| match ty {
+            Type::Tuple(fields) => Ok(Type::Struct {
+                name: name.to_string(),
+                fields,
+            }),
+            ty => Ok(ty),
+        });
+    }
+
+    Ok(ty)
+}
+
 fn param_type_string(ty: &Type) -> String {
     match ty {
-        Type::Tuple(_) => String::from("tuple"),
+        Type::Tuple(_) | Type::Struct { .. } => String::from("tuple"),
         Type::Array(ty) => format!("{}[]", param_type_string(ty)),
         Type::FixedArray(ty, size) => format!("{}[{}]", param_type_string(ty), size),
-        _ => format!("{}", ty),
+        _ => ty.signature(),
     }
 }
 
@@ -162,15 +277,19 @@ struct ParamEntry {
     pub indexed: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Vec<ParamEntry>>,
+    #[serde(rename = "internalType", skip_serializing_if = "Option::is_none")]
+    pub internal_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variants: Option<Vec<String>>,
 }
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{char, digit1},
-    combinator::{all_consuming, map_res, opt, recognize},
-    multi::many1,
-    sequence::delimited,
+    character::complete::{alpha1, alphanumeric1, char, digit1, multispace0, multispace1},
+    combinator::{all_consuming, map_res, opt, recognize, verify},
+    multi::{many0, many1, separated_list0},
+    sequence::{delimited, pair, preceded},
     IResult,
 };
 
@@ -214,7 +333,7 @@ fn parse_type(
 ) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
         alt((
-            parse_array(components.clone()),
+            parse_array(parse_simple_type(components.clone())),
             parse_simple_type(components.clone()),
         ))(input)
     }
@@ -227,8 +346,14 @@ fn parse_simple_type(
         alt((
             parse_tuple(components.clone()),
             parse_fields,
+            parse_fixed_bytes,
+            parse_u8,
+            parse_u16,
             parse_u32,
+            parse_u64,
             parse_u256,
+            parse_i32,
+            parse_i64,
             parse_field,
             parse_address,
             parse_hash,
@@ -242,10 +367,30 @@ fn parse_u32(input: &str) -> TypeParseResult<&str, Type> {
     map_error(tag("u32")(input).map(|(i, _)| (i, Type::U32)))
 }
 
+fn parse_u8(input: &str) -> TypeParseResult<&str, Type> {
+    map_error(tag("u8")(input).map(|(i, _)| (i, Type::U8)))
+}
+
+fn parse_u16(input: &str) -> TypeParseResult<&str, Type> {
+    map_error(tag("u16")(input).map(|(i, _)| (i, Type::U16)))
+}
+
+fn parse_u64(input: &str) -> TypeParseResult<&str, Type> {
+    map_error(tag("u64")(input).map(|(i, _)| (i, Type::U64)))
+}
+
 fn parse_u256(input: &str) -> TypeParseResult<&str, Type> {
     map_error(tag("u256")(input).map(|(i, _)| (i, Type::U256)))
 }
 
+fn parse_i32(input: &str) -> TypeParseResult<&str, Type> {
+    map_error(tag("i32")(input).map(|(i, _)| (i, Type::I32)))
+}
+
+fn parse_i64(input: &str) -> TypeParseResult<&str, Type> {
+    map_error(tag("i64")(input).map(|(i, _)| (i, Type::I64)))
+}
+
 fn parse_field(input: &str) -> TypeParseResult<&str, Type> {
     map_error(tag("field")(input).map(|(i, _)| (i, Type::Field)))
 }
@@ -270,11 +415,22 @@ fn parse_fields(input: &str) -> TypeParseResult<&str, Type> {
     map_error(tag("fields")(input).map(|(i, _)| (i, Type::Fields)))
 }
 
+fn parse_fixed_bytes(input: &str) -> TypeParseResult<&str, Type> {
+    map_error(
+        preceded(
+            tag("bytes"),
+            verify(parse_integer, |size| (1..=32).contains(size)),
+        )(input)
+        .map(|(i, size)| (i, Type::FixedBytes(size as usize))),
+    )
+}
+
+/// Parses an array of the elements the given parser accepts, e.g. `u32[2][]`.
 fn parse_array(
-    components: Rc<Option<Vec<ParamEntry>>>,
+    element: impl Fn(&str) -> TypeParseResult<&str, Type>,
 ) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
-        let (i, ty) = parse_simple_type(components.clone())(input)?;
+        let (i, ty) = element(input)?;
 
         let (i, sizes) = map_error(many1(delimited(char('['), opt(parse_integer), char(']')))(
             i,
@@ -303,10 +459,8 @@ fn parse_tuple(
                 .clone()
                 .into_iter()
                 .try_fold(vec![], |mut param_tys, param| {
-                    let comps = param.components.as_ref().cloned();
-
-                    let ty = match parse_exact_type(Rc::new(comps), &param.type_) {
-                        Ok((_, ty)) => ty,
+                    let ty = match entry_type(&param) {
+                        Ok(ty) => ty,
                         Err(_) => return Err(nom::Err::Failure(TypeParseError::Error)),
                     };
 
@@ -322,6 +476,59 @@ fn parse_tuple(
     }
 }
 
+/// Parses a type that may contain inline tuples, e.g. `(u32,string)[]`.
+///
+/// Inline tuples belong to signatures and human-readable declarations only; JSON
+/// entries spell tuples as `tuple` with `components`.
+fn parse_inline(input: &str) -> TypeParseResult<&str, Type> {
+    alt((
+        parse_array(parse_inline_simple_type),
+        parse_inline_simple_type,
+    ))(input)
+}
+
+fn parse_inline_simple_type(input: &str) -> TypeParseResult<&str, Type> {
+    alt((parse_inline_tuple, parse_simple_type(Rc::new(None))))(input)
+}
+
+/// Parses an inline tuple, e.g. `(u32,string)` or `(u32 id, string name)`.
+fn parse_inline_tuple(input: &str) -> TypeParseResult<&str, Type> {
+    let field = |i| {
+        let (i, ty) = parse_inline(i)?;
+        let (i, name) = opt(preceded(multispace1, parse_identifier))(i)?;
+
+        Ok((i, (name.unwrap_or_default().to_string(), ty)))
+    };
+
+    let (i, fields) = delimited(
+        pair(char('('), multispace0),
+        separated_list0(delimited(multispace0, char(','), multispace0), field),
+        pair(multispace0, char(')')),
+    )(input)?;
+
+    Ok((i, Type::Tuple(fields)))
+}
+
+/// Parses a type without JSON components, e.g. `(u32,string)[]`.
+pub(crate) fn parse_inline_type(input: &str) -> IResult<&str, Type> {
+    parse_inline(input).map_err(|err| {
+        err.map(|err| match err {
+            TypeParseError::NomError(err) => err,
+            TypeParseError::Error => nom::error::Error::new(input, nom::error::ErrorKind::Verify),
+        })
+    })
+}
+
+/// Parses an identifier, e.g. a param or function name.
+pub(crate) fn parse_identifier<'a, E: nom::error::ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, &'a str, E> {
+    recognize(pair(
+        alt((alpha1, tag("_"), tag("$"))),
+        many0(alt((alphanumeric1, tag("_"), tag("$")))),
+    ))(input)
+}
+
 fn parse_integer(input: &str) -> IResult<&str, u64> {
     map_res(recognize(many1(digit1)), str::parse)(input)
 }
@@ -347,7 +554,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::U32,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -370,7 +578,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::U256,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -393,7 +602,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::Field,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -402,6 +612,200 @@ mod test {
         assert_eq!(v, param_json);
     }
 
+    #[test]
+    fn serde_signed() {
+        for (name, type_) in [("i32", Type::I32), ("i64", Type::I64)] {
+            let v = json!({
+                "name": "a",
+                "type": name,
+            });
+
+            let param: Param = serde_json::from_value(v.clone()).expect("param deserialized");
+
+            assert_eq!(
+                param,
+                Param {
+                    name: "a".to_string(),
+                    type_,
+                    indexed: None,
+                    internal_type: None
+                }
+            );
+
+            let param_json = serde_json::to_value(param).expect("param serialized");
+
+            assert_eq!(v, param_json);
+        }
+    }
+
+    #[test]
+    fn serde_sized() {
+        for (name, type_) in [
+            ("u8", Type::U8),
+            ("u16", Type::U16),
+            ("u64", Type::U64),
+            ("bytes1", Type::FixedBytes(1)),
+            ("bytes32", Type::FixedBytes(32)),
+            ("u16[2]", Type::FixedArray(Box::new(Type::U16), 2)),
+        ] {
+            let v = json!({
+                "name": "a",
+                "type": name,
+            });
+
+            let param: Param = serde_json::from_value(v.clone()).expect("param deserialized");
+
+            assert_eq!(
+                param,
+                Param {
+                    name: "a".to_string(),
+                    type_,
+                    indexed: None,
+                    internal_type: None
+                }
+            );
+
+            let param_json = serde_json::to_value(param).expect("param serialized");
+
+            assert_eq!(v, param_json);
+        }
+
+        for name in ["bytes0", "bytes33", "bytes"] {
+            let v = json!({
+                "name": "a",
+                "type": name,
+            });
+
+            assert!(serde_json::from_value::<Param>(v).is_err());
+        }
+    }
+
+    #[test]
+    fn serde_enum() {
+        let color = Type::Enum {
+            name: "Color".to_string(),
+            variants: vec!["Red".to_string(), "Green".to_string()],
+        };
+
+        let v = json!({
+            "name": "a",
+            "type": "u32",
+            "internalType": "enum Color",
+            "variants": ["Red", "Green"],
+        });
+
+        let param: Param = serde_json::from_value(v.clone()).expect("param deserialized");
+
+        assert_eq!(
+            param,
+            Param {
+                name: "a".to_string(),
+                type_: color.clone(),
+                indexed: None,
+                internal_type: Some("enum Color".to_string())
+            }
+        );
+
+        let param_json = serde_json::to_value(param).expect("param serialized");
+
+        assert_eq!(v, param_json);
+
+        let v = json!({
+            "name": "a",
+            "type": "u32[2][]",
+            "internalType": "enum Color[2][]",
+            "variants": ["Red", "Green"],
+        });
+
+        let param: Param = serde_json::from_value(v.clone()).expect("param deserialized");
+
+        assert_eq!(
+            param.type_,
+            Type::Array(Box::new(Type::FixedArray(Box::new(color), 2)))
+        );
+        assert_eq!(param.type_.to_string(), "Color[2][]");
+        assert_eq!(param.type_.signature(), "u32[2][]");
+
+        let param_json = serde_json::to_value(param).expect("param serialized");
+
+        assert_eq!(v, param_json);
+
+        let v = json!({
+            "name": "a",
+            "type": "string",
+            "internalType": "enum Color",
+            "variants": ["Red", "Green"],
+        });
+
+        assert!(serde_json::from_value::<Param>(v).is_err());
+    }
+
+    #[test]
+    fn serde_struct() {
+        let v = json!({
+            "name": "books",
+            "type": "tuple[]",
+            "internalType": "struct Library.Book[]",
+            "components": [
+                {
+                    "name": "id",
+                    "type": "u32",
+                    "internalType": "u32",
+                },
+                {
+                    "name": "author",
+                    "type": "tuple",
+                    "internalType": "struct Author",
+                    "components": [
+                        {
+                            "name": "name",
+                            "type": "string",
+                            "internalType": "string",
+                        },
+                    ],
+                },
+            ],
+        });
+
+        let param: Param = serde_json::from_value(v.clone()).expect("param deserialized");
+
+        let author = Type::Struct {
+            name: "Author".to_string(),
+            fields: vec![("name".to_string(), Type::String)],
+        };
+        assert_eq!(
+            param,
+            Param {
+                name: "books".to_string(),
+                type_: Type::Array(Box::new(Type::Struct {
+                    name: "Library.Book".to_string(),
+                    fields: vec![
+                        ("id".to_string(), Type::U32),
+                        ("author".to_string(), author)
+                    ],
+                })),
+                indexed: None,
+                internal_type: Some("struct Library.Book[]".to_string())
+            }
+        );
+        assert_eq!(param.type_.to_string(), "Library.Book[]");
+        assert_eq!(param.type_.signature(), "(u32,(string))[]");
+
+        let param_json = serde_json::to_value(&param).expect("param serialized");
+
+        assert_eq!(v, param_json);
+
+        let param = Param {
+            internal_type: None,
+            ..param
+        };
+        let param_json = serde_json::to_value(&param).expect("param serialized");
+
+        assert_eq!(param_json["internalType"], "struct Library.Book[]");
+        assert_eq!(param_json["components"][0].get("internalType"), None);
+        assert_eq!(param_json["components"][1]["internalType"], "struct Author");
+    }
+
     #[test]
     fn serde_address() {
         let v = json!({
@@ -416,7 +820,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::Address,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -439,7 +844,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::Bool,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -462,7 +868,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::String,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -485,7 +892,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::Fields,
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -507,7 +915,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::Array(Box::new(Type::U32)),
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -529,7 +938,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::Array(Box::new(Type::Array(Box::new(Type::Address)))),
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -551,7 +961,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::Array(Box::new(Type::FixedArray(Box::new(Type::String), 2))),
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -571,7 +982,8 @@ mod test {
             Param {
                 name: "a".to_string(),
                 type_: Type::FixedArray(Box::new(Type::Array(Box::new(Type::String))), 3),
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
@@ -628,12 +1040,70 @@ mod test {
                         ])))
                     )
                 ]),
-                indexed: None
+                indexed: None,
+                internal_type: None
             }
         );
 
         let param_json = serde_json::to_value(param).expect("param serialized");
 
         assert_eq!(v, param_json);
+
+        // inline tuples are only accepted in signatures and declarations.
+        for name in ["(u32,string)", "(u32,string)[]"] {
+            let v = json!({
+                "name": "s",
+                "type": name,
+            });
+
+            assert!(serde_json::from_value::<Param>(v).is_err());
+        }
+    }
+
+    #[test]
+    fn display_decoded_params() {
+        let param = |name: &str, type_: Type| Param {
+            name: name.to_string(),
+            type_,
+            indexed: None,
+            internal_type: None,
+        };
+
+        let params = DecodedParams::from(vec![
+            (param("id", Type::U32), Value::U32(10)),
+            (
+                param("", Type::Array(Box::new(Type::String))),
+                Value::Array(vec![Value::String("a".to_string())], Type::String),
+            ),
+        ]);
+
+        assert_eq!(params.to_string(), "id: u32 = 10\n1: string[] = [\"a\"]");
+        assert_eq!(params[0].to_string(), "id: u32 = 10");
+
+        let book = Type::Struct {
+            name: "Book".to_string(),
+            fields: vec![
+                ("id".to_string(), Type::U32),
+                ("tags".to_string(), Type::Array(Box::new(Type::String))),
+            ],
+        };
+        let params = DecodedParams::from(vec![(
+            param("books", Type::Array(Box::new(book.clone()))),
+            Value::Array(
+                vec![Value::Tuple(vec![
+                    ("id".to_string(), Value::U32(1)),
+                    (
+                        "tags".to_string(),
+                        Value::Array(vec![Value::String("x".to_string())], Type::String),
+                    ),
+                ])],
+                book,
+            ),
+        )]);
+
+        assert_eq!(
+            params.to_string(),
+            "books: Book[] = [\n    (\n        id: 1,\n        tags: [\"x\"],\n    ),\n]"
+        );
     }
 }
@@ -1,10 +1,20 @@
 /// Available ABI types.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type {
+    /// Unsigned int type uint8.
+    U8,
+    /// Unsigned int type uint16.
+    U16,
     /// Unsigned int type uint32.
     U32,
+    /// Unsigned int type uint64.
+    U64,
     /// Unsigned int type uint256.
     U256,
+    /// Signed int type int32.
+    I32,
+    /// Signed int type int64.
+    I64,
     /// Field
     Field,
     /// Hash type (address).
@@ -13,6 +23,8 @@ pub enum Type {
     Address,
     /// Bool type (bool).
     Bool,
+    /// Fixed size bytes type (bytesN).
+    FixedBytes(usize),
     /// Fixed size array type (T\[k\])
     FixedArray(Box<Type>, u64),
     /// UTF-8 string type (string).
@@ -23,23 +35,66 @@ pub enum Type {
     Array(Box<Type>),
     /// Tuple type (tuple(T1, T2, ..., Tn))
     Tuple(Vec<(String, Type)>),
+    /// Named struct type, encoded as a tuple of its fields.
+    Struct {
+        /// Struct name.
+        name: String,
+        /// Field names and types.
+        fields: Vec<(String, Type)>,
+    },
+    /// Enum type, encoded as the `u32` index of its variant.
+    Enum {
+        /// Enum name.
+        name: String,
+        /// Variant names, in declaration order.
+        variants: Vec<String>,
+    },
 }
 
 impl Type {
     /// Returns whether the given type is a dynamic size type or not.
     pub fn is_dynamic(&self) -> bool {
         match self {
+            Type::U8 => false,
+            Type::U16 => false,
             Type::U32 => false,
+            Type::U64 => false,
             Type::U256 => false,
+            Type::I32 => false,
+            Type::I64 => false,
             Type::Field => false,
             Type::Address => false,
             Type::Hash => false,
             Type::Bool => false,
+            Type::FixedBytes(_) => false,
             Type::FixedArray(ty, _) => ty.is_dynamic(),
             Type::String => true,
             Type::Fields => true,
             Type::Array(_) => true,
-            Type::Tuple(tys) => tys.iter().any(|(_, ty)| ty.is_dynamic()),
+            Type::Tuple(tys) | Type::Struct { fields: tys, .. } => {
+                tys.iter().any(|(_, ty)| ty.is_dynamic())
+            }
+            Type::Enum { .. } => false,
+        }
+    }
+
+    /// Returns the type as written in function, event and error signatures.
+    ///
+    /// Enums appear as their `u32` discriminant, and structs as tuples of their
+    /// field types.
+    pub fn signature(&self) -> String {
+        match self {
+            Type::Enum { .. } => Type::U32.to_string(),
+            Type::FixedArray(ty, size) => format!("{}[{}]", ty.signature(), size),
+            Type::Array(ty) => format!("{}[]", ty.signature()),
+            Type::Tuple(tys) | Type::Struct { fields: tys, .. } => format!(
+                "({})",
+                tys.iter()
+                    .map(|(_, ty)| ty.signature())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            ty => ty.to_string(),
         }
     }
 }
@@ -47,12 +102,18 @@ impl Type {
 impl std::fmt::Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Type::U8 => write!(f, "u8"),
+            Type::U16 => write!(f, "u16"),
             Type::U32 => write!(f, "u32"),
+            Type::U64 => write!(f, "u64"),
             Type::U256 => write!(f, "u256"),
+            Type::I32 => write!(f, "i32"),
+            Type::I64 => write!(f, "i64"),
             Type::Field => write!(f, "field"),
             Type::Hash => write!(f, "hash"),
             Type::Address => write!(f, "address"),
             Type::Bool => write!(f, "bool"),
+            Type::FixedBytes(size) => write!(f, "bytes{}", size),
             Type::String => write!(f, "string"),
             Type::Fields => write!(f, "fields"),
             Type::FixedArray(ty, size) => write!(f, "{}[{}]", ty, size),
@@ -65,6 +126,7 @@ impl std::fmt::Display for Type {
                     .collect::<Vec<_>>()
                     .join(",")
             ),
+            Type::Struct { name, .. } | Type::Enum { name, .. } => write!(f, "{}", name),
         }
     }
 }
@@ -0,0 +1,520 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{escaped_transform, is_not, tag},
+    character::complete::{char, digit1, hex_digit1, multispace0},
+    combinator::{all_consuming, consumed, map, opt, recognize, value},
+    multi::separated_list0,
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+
+use crate::{
+    abi::Function,
+    error::{AbiError, Result},
+    json::parse_hex,
+    params::parse_identifier,
+    types::Type,
+    values::{FixedArray8, Value},
+};
+
+impl Value {
+    /// Parses a value of the given type from a command-line style string.
+    ///
+    /// The type disambiguates the literals:
+    ///
+    /// - integers and fields are decimal or `0x`-prefixed hex numbers, e.g.
+    ///   `42`, `-7` or `0x2a`, where only decimal numbers may be negative;
+    /// - `u256` is a decimal or hex number of up to 256 bits;
+    /// - `address` and `hash` are hex numbers, e.g. `0xabc`;
+    /// - `bytesN` is a hex string of exactly `2 * N` digits;
+    /// - `bool` is `true` or `false`;
+    /// - `string` is a double-quoted string, or a bare word such as `bob`;
+    /// - enums are the variant name;
+    /// - arrays and `fields` are written `[1, 2, 3]`, tuples `("bob", [7, 8])`.
+    ///
+    /// ```
+    /// use ola_lang_abi::{Type, Value};
+    ///
+    /// let ty = Type::Tuple(vec![
+    ///     ("name".to_string(), Type::String),
+    ///     ("ids".to_string(), Type::Array(Box::new(Type::U32))),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     Value::parse(r#"("bob", [7, 8])"#, &ty).unwrap(),
+    ///     Value::Tuple(vec![
+    ///         ("name".to_string(), Value::String("bob".to_string())),
+    ///         (
+    ///             "ids".to_string(),
+    ///             Value::Array(vec![Value::U32(7), Value::U32(8)], Type::U32)
+    ///         ),
+    ///     ])
+    /// );
+    /// ```
+    pub fn parse(input: &str, ty: &Type) -> Result<Self> {
+        Self::parse_at(input, ty, "$")
+    }
+
+    fn parse_at(input: &str, ty: &Type, path: &str) -> Result<Self> {
+        let (_, literal) = all_consuming(delimited(multispace0, parse_literal, multispace0))(input)
+            .map_err(
+                |_: nom::Err<nom::error::Error<&str>>| AbiError::InvalidValue {
+                    path: path.to_string(),
+                    ty: Box::new(ty.clone()),
+                    input: input.to_string(),
+                },
+            )?;
+
+        literal.to_value(ty, path)
+    }
+}
+
+impl Function {
+    /// Parses the function arguments from command-line style strings, one per
+    /// input param.
+    ///
+    /// See [`Value::parse`] for the accepted syntax.
+    pub fn parse_inputs(&self, args: &[&str]) -> Result<Vec<Value>> {
+        if args.len() != self.inputs.len() {
+            return Err(AbiError::LengthMismatch {
+                path: "inputs".to_string(),
+                expected: self.inputs.len(),
+                found: args.len(),
+            });
+        }
+
+        args.iter()
+            .zip(&self.inputs)
+            .enumerate()
+            .map(|(i, (arg, param))| Value::parse_at(arg, &param.type_, &format!("inputs[{}]", i)))
+            .collect()
+    }
+}
+
+/// An untyped literal along with its source text.
+#[derive(Debug)]
+struct Literal<'a> {
+    text: &'a str,
+    kind: LiteralKind<'a>,
+}
+
+#[derive(Debug)]
+enum LiteralKind<'a> {
+    /// Decimal number, possibly negative.
+    Number(&'a str),
+    /// `0x`-prefixed hex number, without the prefix.
+    Hex(&'a str),
+    /// Double-quoted string, unescaped.
+    Str(String),
+    /// Bare word, e.g. `true` or an enum variant.
+    Word(&'a str),
+    List(Vec<Literal<'a>>),
+    Tuple(Vec<Literal<'a>>),
+}
+
+impl<'a> Literal<'a> {
+    fn to_value(&self, ty: &Type, path: &str) -> Result<Value> {
+        let invalid = || AbiError::InvalidValue {
+            path: path.to_string(),
+            ty: Box::new(ty.clone()),
+            input: self.text.to_string(),
+        };
+
+        let value = match ty {
+            Type::U8 => Value::U8(
+                self.as_u64()
+                    .and_then(|i| i.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            Type::U16 => Value::U16(
+                self.as_u64()
+                    .and_then(|i| i.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            Type::U32 => Value::U32(
+                self.as_u64()
+                    .filter(|i| *i <= u32::MAX as u64)
+                    .ok_or_else(invalid)?,
+            ),
+            Type::U64 => Value::U64(self.as_u64().ok_or_else(invalid)?),
+            Type::I32 => Value::I32(
+                self.as_i64()
+                    .and_then(|i| i.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            Type::I64 => Value::I64(self.as_i64().ok_or_else(invalid)?),
+            Type::Field => Value::Field(self.as_field().ok_or_else(invalid)?),
+            Type::U256 => Value::U256(
+                match &self.kind {
+                    LiteralKind::Number(digits) => FixedArray8::from_dec_str(digits).ok(),
+                    LiteralKind::Hex(digits) => digits.parse().ok(),
+                    _ => None,
+                }
+                .ok_or_else(invalid)?,
+            ),
+            Type::Address => Value::Address(self.as_hex().ok_or_else(invalid)?),
+            Type::Hash => Value::Hash(self.as_hex().ok_or_else(invalid)?),
+            Type::FixedBytes(size) => Value::FixedBytes(
+                match &self.kind {
+                    LiteralKind::Hex(_) => parse_hex(self.text),
+                    _ => None,
+                }
+                .filter(|bytes| bytes.len() == *size)
+                .ok_or_else(invalid)?,
+            ),
+            Type::Bool => Value::Bool(match self.kind {
+                LiteralKind::Word("true") => true,
+                LiteralKind::Word("false") => false,
+                _ => return Err(invalid()),
+            }),
+            Type::String => Value::String(match &self.kind {
+                LiteralKind::Str(s) => s.clone(),
+                LiteralKind::Word(word) => word.to_string(),
+                _ => return Err(invalid()),
+            }),
+            Type::Fields => match &self.kind {
+                LiteralKind::List(literals) => Value::Fields(
+                    literals
+                        .iter()
+                        .enumerate()
+                        .map(|(i, literal)| {
+                            literal.as_field().ok_or_else(|| AbiError::InvalidValue {
+                                path: format!("{}[{}]", path, i),
+                                ty: Box::new(Type::Field),
+                                input: literal.text.to_string(),
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                ),
+                _ => return Err(invalid()),
+            },
+            Type::FixedArray(elem_ty, size) => match &self.kind {
+                LiteralKind::List(literals) => {
+                    if literals.len() as u64 != *size {
+                        return Err(AbiError::LengthMismatch {
+                            path: path.to_string(),
+                            expected: *size as usize,
+                            found: literals.len(),
+                        });
+                    }
+
+                    Value::FixedArray(to_values(literals, elem_ty, path)?, *elem_ty.clone())
+                }
+                _ => return Err(invalid()),
+            },
+            Type::Array(elem_ty) => match &self.kind {
+                LiteralKind::List(literals) => {
+                    Value::Array(to_values(literals, elem_ty, path)?, *elem_ty.clone())
+                }
+                _ => return Err(invalid()),
+            },
+            Type::Tuple(fields) | Type::Struct { fields, .. } => match &self.kind {
+                LiteralKind::Tuple(literals) => {
+                    if literals.len() != fields.len() {
+                        return Err(AbiError::LengthMismatch {
+                            path: path.to_string(),
+                            expected: fields.len(),
+                            found: literals.len(),
+                        });
+                    }
+
+                    Value::Tuple(
+                        fields
+                            .iter()
+                            .zip(literals)
+                            .enumerate()
+                            .map(|(i, ((name, ty), literal))| {
+                                let field = if name.is_empty() {
+                                    i.to_string()
+                                } else {
+                                    name.clone()
+                                };
+                                let value = literal.to_value(ty, &format!("{}.{}", path, field))?;
+
+                                Ok((name.clone(), value))
+                            })
+                            .collect::<Result<_>>()?,
+                    )
+                }
+                _ => return Err(invalid()),
+            },
+            Type::Enum { variants, .. } => match self.kind {
+                LiteralKind::Word(variant) if variants.iter().any(|v| v == variant) => {
+                    Value::Enum(variant.to_string(), ty.clone())
+                }
+                _ => return Err(invalid()),
+            },
+        };
+
+        Ok(value)
+    }
+
+    /// Returns the value of a decimal or hex number.
+    fn as_u64(&self) -> Option<u64> {
+        match self.kind {
+            LiteralKind::Number(digits) => digits.parse().ok(),
+            LiteralKind::Hex(digits) => u64::from_str_radix(digits, 16).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a possibly negative decimal number, or a hex number.
+    fn as_i64(&self) -> Option<i64> {
+        match self.kind {
+            LiteralKind::Number(digits) => digits.parse().ok(),
+            LiteralKind::Hex(digits) => i64::from_str_radix(digits, 16).ok(),
+            _ => None,
+        }
+    }
+
+    fn as_field(&self) -> Option<u64> {
+        self.as_u64().filter(|i| Value::field(*i).is_ok())
+    }
+
+    fn as_hex<T: std::str::FromStr>(&self) -> Option<T> {
+        match self.kind {
+            LiteralKind::Hex(_) => self.text.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+fn to_values(literals: &[Literal], ty: &Type, path: &str) -> Result<Vec<Value>> {
+    literals
+        .iter()
+        .enumerate()
+        .map(|(i, literal)| literal.to_value(ty, &format!("{}[{}]", path, i)))
+        .collect()
+}
+
+fn parse_literal(input: &str) -> IResult<&str, Literal<'_>> {
+    map(
+        consumed(alt((
+            map(parse_list('[', ']'), LiteralKind::List),
+            map(parse_list('(', ')'), LiteralKind::Tuple),
+            map(preceded(tag("0x"), hex_digit1), LiteralKind::Hex),
+            map(recognize(pair(opt(char('-')), digit1)), LiteralKind::Number),
+            map(parse_quoted, LiteralKind::Str),
+            map(parse_identifier, LiteralKind::Word),
+        ))),
+        |(text, kind)| Literal { text, kind },
+    )(input)
+}
+
+fn parse_list(open: char, close: char) -> impl Fn(&str) -> IResult<&str, Vec<Literal<'_>>> {
+    move |input: &str| {
+        delimited(
+            pair(char(open), multispace0),
+            separated_list0(
+                delimited(multispace0, char(','), multispace0),
+                parse_literal,
+            ),
+            pair(multispace0, char(close)),
+        )(input)
+    }
+}
+
+/// Parses a double-quoted string, unescaping `\"`, `\\`, `\n` and `\t`.
+fn parse_quoted(input: &str) -> IResult<&str, String> {
+    let escaped = escaped_transform(
+        is_not("\\\""),
+        '\\',
+        alt((
+            value("\\", char('\\')),
+            value("\"", char('"')),
+            value("\n", char('n')),
+            value("\t", char('t')),
+        )),
+    );
+
+    map(
+        delimited(char('"'), opt(escaped), char('"')),
+        Option::unwrap_or_default,
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::FixedArray4;
+
+    #[test]
+    fn parse_scalars() {
+        let cases = vec![
+            ("42", Type::U32, Value::U32(42)),
+            ("0x2a", Type::U8, Value::U8(42)),
+            ("-7", Type::I64, Value::I64(-7)),
+            (
+                "18446744069414584320",
+                Type::Field,
+                Value::Field(18446744069414584320),
+            ),
+            (
+                "340282366920938463463374607431768211456",
+                Type::U256,
+                Value::U256(FixedArray8([0, 0, 0, 1, 0, 0, 0, 0])),
+            ),
+            (
+                "0xabc",
+                Type::Hash,
+                Value::Hash(FixedArray4([0, 0, 0, 0xabc])),
+            ),
+            (
+                "0xab01",
+                Type::FixedBytes(2),
+                Value::FixedBytes(vec![0xab, 0x01]),
+            ),
+            (" true ", Type::Bool, Value::Bool(true)),
+            (
+                r#""a \"b\"\n""#,
+                Type::String,
+                Value::String("a \"b\"\n".to_string()),
+            ),
+            (r#""""#, Type::String, Value::String(String::new())),
+            ("bob", Type::String, Value::String("bob".to_string())),
+            ("[1, 0x2]", Type::Fields, Value::Fields(vec![1, 2])),
+        ];
+
+        for (input, ty, expected) in cases {
+            assert_eq!(Value::parse(input, &ty).unwrap(), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn parse_composites() {
+        let color = Type::Enum {
+            name: "Color".to_string(),
+            variants: vec!["Red".to_string(), "Green".to_string()],
+        };
+        let ty = Type::FixedArray(
+            Box::new(Type::Tuple(vec![
+                ("".to_string(), color.clone()),
+                ("ids".to_string(), Type::Array(Box::new(Type::U32))),
+            ])),
+            2,
+        );
+
+        assert_eq!(
+            Value::parse("[(Green, [1,2,3]), ( Red , [ ] )]", &ty).unwrap(),
+            Value::FixedArray(
+                vec![
+                    Value::Tuple(vec![
+                        (
+                            "".to_string(),
+                            Value::Enum("Green".to_string(), color.clone())
+                        ),
+                        (
+                            "ids".to_string(),
+                            Value::Array(
+                                vec![Value::U32(1), Value::U32(2), Value::U32(3)],
+                                Type::U32
+                            )
+                        ),
+                    ]),
+                    Value::Tuple(vec![
+                        (
+                            "".to_string(),
+                            Value::Enum("Red".to_string(), color.clone())
+                        ),
+                        ("ids".to_string(), Value::Array(vec![], Type::U32)),
+                    ]),
+                ],
+                Type::Tuple(vec![
+                    ("".to_string(), color),
+                    ("ids".to_string(), Type::Array(Box::new(Type::U32))),
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let ty = Type::Array(Box::new(Type::Tuple(vec![
+            ("".to_string(), Type::Bool),
+            ("ids".to_string(), Type::FixedArray(Box::new(Type::U8), 2)),
+        ])));
+        let invalid = |path: &str, ty: Type, input: &str| AbiError::InvalidValue {
+            path: path.to_string(),
+            ty: Box::new(ty),
+            input: input.to_string(),
+        };
+
+        assert_eq!(
+            Value::parse("[(true, [1, 256])]", &ty).unwrap_err(),
+            invalid("$[0].ids[1]", Type::U8, "256")
+        );
+        assert_eq!(
+            Value::parse("[(yes, [1, 2])]", &ty).unwrap_err(),
+            invalid("$[0].0", Type::Bool, "yes")
+        );
+        assert_eq!(
+            Value::parse("[(true, [1])]", &ty).unwrap_err(),
+            AbiError::LengthMismatch {
+                path: "$[0].ids".to_string(),
+                expected: 2,
+                found: 1,
+            }
+        );
+        assert_eq!(
+            Value::parse("[(true, [1, 2])", &ty).unwrap_err(),
+            invalid("$", ty.clone(), "[(true, [1, 2])")
+        );
+        assert_eq!(
+            Value::parse("42", &Type::Address).unwrap_err(),
+            invalid("$", Type::Address, "42")
+        );
+        assert_eq!(
+            Value::parse("0xabc", &Type::U256).unwrap(),
+            Value::U256(FixedArray8([0, 0, 0, 0, 0, 0, 0, 0xabc]))
+        );
+    }
+
+    #[test]
+    fn parse_signed_hex() {
+        assert_eq!(Value::parse("0x2a", &Type::I32).unwrap(), Value::I32(42));
+        assert_eq!(
+            Value::parse("0x7fffffffffffffff", &Type::I64).unwrap(),
+            Value::I64(i64::MAX)
+        );
+        assert_eq!(
+            Value::parse("0x80000000", &Type::I32).unwrap_err(),
+            AbiError::InvalidValue {
+                path: "$".to_string(),
+                ty: Box::new(Type::I32),
+                input: "0x80000000".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_function_inputs() {
+        let function = Function::from_signature("transfer(address,u256,string)").unwrap();
+
+        assert_eq!(
+            function.parse_inputs(&["0x1", "100", r#""memo""#]).unwrap(),
+            vec![
+                Value::Address(FixedArray4([0, 0, 0, 1])),
+                Value::U256(FixedArray8([0, 0, 0, 0, 0, 0, 0, 100])),
+                Value::String("memo".to_string()),
+            ]
+        );
+        assert_eq!(
+            function.parse_inputs(&["0x1", "-1", "memo"]).unwrap_err(),
+            AbiError::InvalidValue {
+                path: "inputs[1]".to_string(),
+                ty: Box::new(Type::U256),
+                input: "-1".to_string(),
+            }
+        );
+        assert_eq!(
+            function.parse_inputs(&["0x1"]).unwrap_err(),
+            AbiError::LengthMismatch {
+                path: "inputs".to_string(),
+                expected: 3,
+                found: 1,
+            }
+        );
+    }
+}
@@ -1,31 +1,107 @@
-use anyhow::{anyhow, Result};
+use crate::{
+    error::{AbiError, FromDecError, FromHexError, Result},
+    types::Type,
+};
+use mini_goldilocks::{
+    field::Field64,
+    goldilocks::GoldilocksField,
+    poseidon::{poseidon_u64, unsafe_poseidon_bytes_auto_padded},
+};
+use std::{fmt, str::FromStr};
+
+/// Number of hex digits in a 32-byte value.
+const HEX_DIGITS: usize = 64;
+
+/// Parses a hex string, with or without `0x` prefix, into a 32-byte big-endian buffer.
+///
+/// Shorter strings are left-padded with zeros unless `exact` is set, in which
+/// case exactly 64 hex digits are required.
+fn parse_hex_bytes(s: &str, exact: bool) -> Result<[u8; 32], FromHexError> {
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+
+    if let Some((index, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(FromHexError::InvalidChar {
+            c,
+            index: index + (s.len() - digits.len()),
+        });
+    }
+
+    if exact && digits.len() != HEX_DIGITS {
+        return Err(FromHexError::InvalidLength {
+            expected: HEX_DIGITS,
+            found: digits.len(),
+        });
+    }
+
+    if digits.len() > HEX_DIGITS {
+        return Err(FromHexError::TooLong {
+            max: HEX_DIGITS,
+            found: digits.len(),
+        });
+    }
+
+    let mut bytes = [0u8; 32];
+    // walk digits from the least significant end, two nibbles per byte
+    for (i, nibble) in digits.bytes().rev().enumerate() {
+        let value = (nibble as char).to_digit(16).unwrap_or_default() as u8;
+        bytes[31 - i / 2] |= value << (4 * (i % 2));
+    }
 
-use crate::types::Type;
-use std::fmt;
+    Ok(bytes)
+}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FixedArray4(pub [u64; 4]);
 
-impl From<&str> for FixedArray4 {
-    fn from(s: &str) -> Self {
-        let cleaned = s.trim_start_matches("0x");
-        let padded = format!("{:0>64}", cleaned);
+impl FromStr for FixedArray4 {
+    type Err = FromHexError;
+
+    /// Parses a hex string of at most 64 digits, left-padding it with zeros.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hex_bytes(s, false).map(|bytes| Self::from_bytes(&bytes))
+    }
+}
+
+impl TryFrom<&str> for FixedArray4 {
+    type Error = FromHexError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl FixedArray4 {
+    /// Parses a hex string that holds exactly 64 digits.
+    pub fn from_hex_exact(s: &str) -> Result<Self, FromHexError> {
+        parse_hex_bytes(s, true).map(|bytes| Self::from_bytes(&bytes))
+    }
+
+    /// Builds the array from a 32-byte big-endian buffer.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
         let mut result = [0; 4];
-        for (i, chunk) in padded.as_bytes().rchunks(16).rev().enumerate() {
-            let chunk_str = std::str::from_utf8(chunk).expect("Invalid UTF-8");
-            result[i] =
-                u64::from_str_radix(chunk_str, 16).expect("Failed to parse hex string") as u64;
+        for (value, chunk) in result.iter_mut().zip(bytes.chunks_exact(8)) {
+            *value = u64::from_be_bytes(chunk.try_into().unwrap());
         }
         FixedArray4(result)
     }
-}
 
-impl FixedArray4 {
+    /// Returns the array as a 32-byte big-endian buffer.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, value) in bytes.chunks_exact_mut(8).zip(self.0.iter()) {
+            chunk.copy_from_slice(&value.to_be_bytes());
+        }
+        bytes
+    }
+
     pub fn to_hex_string(&self) -> String {
         let mut hex_string = String::with_capacity(66); // 64 for data + 2 for "0x" prefix
         hex_string.push_str("0x");
         for &value in self.0.iter() {
-            hex_string.push_str(&format!("{:016x}", value as u64));
+            hex_string.push_str(&format!("{:016x}", value));
         }
         hex_string
     }
@@ -35,30 +111,244 @@ impl fmt::Display for FixedArray4 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "0x")?;
         for &value in self.0.iter() {
-            write!(f, "{:016x}", value as u64)?;
+            write!(f, "{:016x}", value)?;
         }
         Ok(())
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A 256-bit value stored as eight 32-bit limbs, most significant first.
+///
+/// Values order numerically as long as every limb fits in 32 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FixedArray8(pub [u64; 8]);
 
-impl From<&str> for FixedArray8 {
-    fn from(s: &str) -> Self {
-        let cleaned = s.trim_start_matches("0x");
-        let padded = format!("{:0>64}", cleaned);
+impl From<u64> for FixedArray8 {
+    fn from(value: u64) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl From<u128> for FixedArray8 {
+    fn from(value: u128) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[16..].copy_from_slice(&value.to_be_bytes());
+        Self::from_be_bytes(&bytes)
+    }
+}
+
+impl FromStr for FixedArray8 {
+    type Err = FromHexError;
+
+    /// Parses a hex string of at most 64 digits, left-padding it with zeros.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hex_bytes(s, false).map(|bytes| Self::from_be_bytes(&bytes))
+    }
+}
+
+impl TryFrom<&str> for FixedArray8 {
+    type Error = FromHexError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl FixedArray8 {
+    /// Parses a hex string that holds exactly 64 digits.
+    pub fn from_hex_exact(s: &str) -> Result<Self, FromHexError> {
+        parse_hex_bytes(s, true).map(|bytes| Self::from_be_bytes(&bytes))
+    }
+
+    /// Zero.
+    pub const ZERO: Self = FixedArray8([0; 8]);
+    /// The largest 256-bit value.
+    pub const MAX: Self = FixedArray8([u32::MAX as u64; 8]);
+
+    /// Builds the array from limbs, rejecting limbs above `u32::MAX`.
+    pub fn from_limbs(limbs: [u64; 8]) -> Result<Self> {
+        match limbs.iter().position(|limb| *limb > u32::MAX as u64) {
+            Some(index) => Err(AbiError::InvalidLimb {
+                index,
+                limb: limbs[index],
+            }),
+            None => Ok(FixedArray8(limbs)),
+        }
+    }
+
+    /// Parses a decimal string.
+    pub fn from_dec_str(s: &str) -> Result<Self, FromDecError> {
+        if s.is_empty() {
+            return Err(FromDecError::Empty);
+        }
+
+        s.char_indices().try_fold(Self::ZERO, |acc, (index, c)| {
+            let digit = c
+                .to_digit(10)
+                .ok_or(FromDecError::InvalidChar { c, index })?;
+
+            acc.checked_mul(&Self::from(10u64))
+                .and_then(|acc| acc.checked_add(&Self::from(digit as u64)))
+                .ok_or(FromDecError::Overflow)
+        })
+    }
+
+    /// Returns the value as a decimal string.
+    pub fn to_dec_string(&self) -> String {
+        let mut limbs = self.le_limbs();
+        let mut chunks = vec![];
+        loop {
+            // divide by 10^9, collecting the remainders as 9-digit chunks
+            let mut rem = 0u64;
+            for limb in limbs.iter_mut().rev() {
+                let cur = (rem << 32) | *limb as u64;
+                *limb = (cur / 1_000_000_000) as u32;
+                rem = cur % 1_000_000_000;
+            }
+            chunks.push(rem);
+
+            if limbs.iter().all(|limb| *limb == 0) {
+                break;
+            }
+        }
+
+        let mut s = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            s.push_str(&format!("{:09}", chunk));
+        }
+        s
+    }
+
+    /// Returns the value as a `u64`, or `None` if it does not fit.
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|value| value.try_into().ok())
+    }
+
+    /// Returns the value as a `u128`, or `None` if it does not fit.
+    pub fn to_u128(&self) -> Option<u128> {
+        let bytes = self.to_be_bytes();
+        if bytes[..16].iter().any(|b| *b != 0) {
+            return None;
+        }
+        Some(u128::from_be_bytes(bytes[16..].try_into().unwrap()))
+    }
+
+    /// Returns whether the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.le_limbs().iter().all(|limb| *limb == 0)
+    }
+
+    /// Adds two values, returning `None` on overflow.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let (a, b) = (self.le_limbs(), other.le_limbs());
+        let mut result = [0u32; 8];
+        let mut carry = 0u64;
+        for i in 0..8 {
+            let sum = a[i] as u64 + b[i] as u64 + carry;
+            result[i] = sum as u32;
+            carry = sum >> 32;
+        }
+
+        (carry == 0).then(|| Self::from_le_limbs(result))
+    }
+
+    /// Subtracts `other` from the value, returning `None` on underflow.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let (a, b) = (self.le_limbs(), other.le_limbs());
+        let mut result = [0u32; 8];
+        let mut borrow = false;
+        for i in 0..8 {
+            let (diff, borrow1) = a[i].overflowing_sub(b[i]);
+            let (diff, borrow2) = diff.overflowing_sub(borrow as u32);
+            result[i] = diff;
+            borrow = borrow1 || borrow2;
+        }
+
+        (!borrow).then(|| Self::from_le_limbs(result))
+    }
+
+    /// Multiplies two values, returning `None` on overflow.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let (a, b) = (self.le_limbs(), other.le_limbs());
+        let mut result = [0u32; 16];
+        for i in 0..8 {
+            let mut carry = 0u64;
+            for j in 0..8 {
+                let cur = result[i + j] as u64 + a[i] as u64 * b[j] as u64 + carry;
+                result[i + j] = cur as u32;
+                carry = cur >> 32;
+            }
+            result[i + 8] = carry as u32;
+        }
+
+        if result[8..].iter().any(|limb| *limb != 0) {
+            return None;
+        }
+        Some(Self::from_le_limbs(result[..8].try_into().unwrap()))
+    }
+
+    /// Alias of [`Self::from_be_bytes`], matching [`FixedArray4::from_bytes`].
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self::from_be_bytes(bytes)
+    }
+
+    /// Builds the array from a 32-byte big-endian buffer.
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Self {
         let mut result = [0; 8];
-        for (i, chunk) in padded.as_bytes().rchunks(8).rev().enumerate() {
-            let chunk_str = std::str::from_utf8(chunk).expect("Invalid UTF-8");
-            result[i] =
-                u64::from_str_radix(chunk_str, 16).expect("Failed to parse hex string") as u64;
+        for (value, chunk) in result.iter_mut().zip(bytes.chunks_exact(4)) {
+            *value = u32::from_be_bytes(chunk.try_into().unwrap()) as u64;
         }
         FixedArray8(result)
     }
-}
 
-impl FixedArray8 {
+    /// Builds the array from a 32-byte little-endian buffer.
+    pub fn from_le_bytes(bytes: &[u8; 32]) -> Self {
+        let mut bytes = *bytes;
+        bytes.reverse();
+        Self::from_be_bytes(&bytes)
+    }
+
+    /// Alias of [`Self::to_be_bytes`], matching [`FixedArray4::to_bytes`].
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.to_be_bytes()
+    }
+
+    /// Returns the array as a 32-byte big-endian buffer.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(self.0.iter()) {
+            chunk.copy_from_slice(&(*value as u32).to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the array as a 32-byte little-endian buffer.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = self.to_be_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns the 32-bit limbs, least significant first.
+    fn le_limbs(&self) -> [u32; 8] {
+        let mut limbs = self.0.map(|limb| limb as u32);
+        limbs.reverse();
+        limbs
+    }
+
+    /// Builds the array from 32-bit limbs, least significant first.
+    fn from_le_limbs(mut limbs: [u32; 8]) -> Self {
+        limbs.reverse();
+        FixedArray8(limbs.map(|limb| limb as u64))
+    }
+
+    /// Returns the limbs as field elements, most significant first.
+    ///
+    /// Every 32-bit limb is a canonical field element.
+    pub fn to_field_elements(&self) -> [GoldilocksField; 8] {
+        self.0.map(|limb| GoldilocksField(limb as u32 as u64))
+    }
+
     pub fn to_hex_string(&self) -> String {
         let mut hex_string = String::with_capacity(66); // 64 for data + 2 for "0x" prefix
         hex_string.push_str("0x");
@@ -79,14 +369,74 @@ impl fmt::Display for FixedArray8 {
     }
 }
 
+/// Order of the Goldilocks field, the largest canonical field value plus one.
+const FIELD_ORDER: u64 = <GoldilocksField as Field64>::ORDER;
+
+/// Converts a word into a field element, rejecting values at or above the modulus.
+fn to_field_element(word: u64) -> Result<GoldilocksField> {
+    if word >= FIELD_ORDER {
+        return Err(AbiError::InvalidField(word));
+    }
+
+    Ok(GoldilocksField(word))
+}
+
+/// Options controlling how values are decoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Reject non-canonical encodings: `bool` words other than 0 and 1, `u8`, `u16`,
+    /// `u32` and `i32` words above their type's maximum, `u64`, `i64` and `u256`
+    /// limbs above `u32::MAX`, `field` values at or above the Goldilocks modulus,
+    /// string and `bytesN` bytes above `u8::MAX`, and unconsumed trailing words.
+    ///
+    /// Disabled by default, in which case such inputs are decoded leniently.
+    pub strict: bool,
+}
+
+impl DecodeOptions {
+    /// Options for strict decoding.
+    pub fn strict() -> Self {
+        DecodeOptions { strict: true }
+    }
+
+    /// In strict mode, rejects a word of type `ty` at `offset` that is above `max`.
+    fn check(&self, ty: &Type, offset: usize, word: u64, max: u64) -> Result<()> {
+        if self.strict && word > max {
+            return Err(AbiError::NonCanonical {
+                ty: ty.clone(),
+                offset,
+                word,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 /// ABI decoded value.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Value {
+    /// Unsigned int value (uint8).
+    U8(u8),
+    /// Unsigned int value (uint16).
+    U16(u16),
     /// Unsigned int value (uint32).
     U32(u64),
+    /// Unsigned int value (uint64).
+    U64(u64),
     /// Unsigned int value (uint256).
     U256(FixedArray8),
-    /// Signed int value (int<M>).
+    /// Signed int value (int32).
+    ///
+    /// The VM has no signed words, so the value travels as the `u32` holding
+    /// its two's complement bits and fits the same range check.
+    I32(i32),
+    /// Signed int value (int64).
+    ///
+    /// Encoded like the `u64` holding its two's complement bits: two 32-bit
+    /// limbs, most significant first, the limb order `u256` uses.
+    I64(i64),
+    /// Goldilocks field element value (field).
     Field(u64),
     /// Address value (address).
     Address(FixedArray4),
@@ -94,6 +444,8 @@ pub enum Value {
     Hash(FixedArray4),
     /// Bool value (bool).
     Bool(bool),
+    /// Fixed size bytes value (bytesN).
+    FixedBytes(Vec<u8>),
 
     /// Fixed size array value (T\[k\]).
     FixedArray(Vec<Value>, Type),
@@ -107,133 +459,326 @@ pub enum Value {
     ///
     /// This variant's vector items have the form (name, value).
     Tuple(Vec<(String, Value)>),
+    /// Enum value, holding the variant name and the enum type.
+    Enum(String, Type),
 }
 
 impl Value {
+    /// Builds a [`Value::Field`], rejecting values at or above the Goldilocks modulus.
+    pub fn field(value: u64) -> Result<Self> {
+        to_field_element(value).map(Value::from)
+    }
+
+    /// Builds a [`Value::Fields`], rejecting values at or above the Goldilocks modulus.
+    pub fn fields(values: Vec<u64>) -> Result<Self> {
+        values
+            .iter()
+            .try_for_each(|value| to_field_element(*value).map(|_| ()))?;
+
+        Ok(Value::Fields(values))
+    }
+
+    /// Builds a [`Value::Fields`] from field elements.
+    ///
+    /// `Value::from` on a `Vec` builds a [`Value::Array`] instead.
+    pub fn from_field_elements(values: Vec<GoldilocksField>) -> Self {
+        Value::Fields(
+            values
+                .into_iter()
+                .map(|value| value.to_canonical_u64())
+                .collect(),
+        )
+    }
+
+    /// Returns the field element held by a [`Value::Field`].
+    ///
+    /// Returns `None` for other values and for non-canonical field values.
+    pub fn as_field_element(&self) -> Option<GoldilocksField> {
+        match self {
+            Value::Field(value) => to_field_element(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Encodes values into field elements.
+    ///
+    /// This is [`Value::encode`] with every word checked against the Goldilocks
+    /// modulus, e.g. a `u256` becomes its eight 32-bit limbs and a string its
+    /// length followed by one element per byte.
+    pub fn encode_field_elements(values: &[Self]) -> Result<Vec<GoldilocksField>> {
+        Self::encode(values)?
+            .into_iter()
+            .map(to_field_element)
+            .collect()
+    }
+
     /// Decodes values from bytes using the given type hint.
     pub fn decode_from_slice(bs: &[u64], tys: &[Type]) -> Result<Vec<Value>> {
-        tys.iter()
-            .try_fold((vec![], 0), |(mut values, at), ty| {
-                let (value, consumed) = Self::decode(bs, ty, 0, at)?;
-                values.push(value);
+        Self::decode_from_slice_with_options(bs, tys, &DecodeOptions::default())
+    }
+
+    /// Decodes values from bytes using the given type hint and decoding options.
+    ///
+    /// In strict mode, the values must consume the whole input.
+    pub fn decode_from_slice_with_options(
+        bs: &[u64],
+        tys: &[Type],
+        opts: &DecodeOptions,
+    ) -> Result<Vec<Value>> {
+        let (values, consumed) = Self::decode_from_slice_consumed(bs, tys, opts)?;
+
+        if opts.strict && consumed != bs.len() {
+            return Err(AbiError::TrailingData { offset: consumed });
+        }
+
+        Ok(values)
+    }
 
-                Ok((values, at + consumed))
-            })
-            .map(|(values, _)| values)
+    /// Decodes values like [`Value::decode_from_slice`], also returning the number
+    /// of words consumed.
+    pub(crate) fn decode_from_slice_consumed(
+        bs: &[u64],
+        tys: &[Type],
+        opts: &DecodeOptions,
+    ) -> Result<(Vec<Value>, usize)> {
+        tys.iter().try_fold((vec![], 0), |(mut values, at), ty| {
+            let (value, consumed) = Self::decode(bs, ty, 0, at, opts)?;
+            values.push(value);
+
+            Ok((values, at + consumed))
+        })
     }
 
     /// Encodes values into bytes.
-    pub fn encode(values: &[Self]) -> Vec<u64> {
+    ///
+    /// Fails on an enum value whose variant its enum type does not declare, naming
+    /// the value by its path, e.g. `$[1].color`.
+    pub fn encode(values: &[Self]) -> Result<Vec<u64>> {
         let mut buf = vec![];
-        for value in values {
-            match value {
-                Value::U32(i) => {
-                    let start = buf.len();
-                    buf.resize(start + 1, *i);
-                }
+        for (i, value) in values.iter().enumerate() {
+            value.encode_into(&mut buf, &format!("$[{}]", i))?;
+        }
 
-                Value::U256(num) => {
-                    let start = buf.len();
-                    buf.resize(start + 8, 0);
+        Ok(buf)
+    }
 
-                    // big-endian, as if it were a uint160.
-                    buf[start..(start + 8)].copy_from_slice(&num.0);
-                }
+    /// Appends the encoding of the value to `buf`, `path` naming the value in errors.
+    fn encode_into(&self, buf: &mut Vec<u64>, path: &str) -> Result<()> {
+        match self {
+            Value::U8(i) => buf.push(*i as u64),
 
-                Value::Field(i) => {
-                    let start = buf.len();
-                    buf.resize(start + 1, *i);
-                }
+            Value::U16(i) => buf.push(*i as u64),
 
-                Value::Address(addr) => {
-                    let start = buf.len();
-                    buf.resize(start + 4, 0);
+            Value::U32(i) => {
+                let start = buf.len();
+                buf.resize(start + 1, *i);
+            }
 
-                    // big-endian, as if it were a uint160.
-                    buf[start..(start + 4)].copy_from_slice(&addr.0);
-                }
+            Value::U64(i) => {
+                // two 32-bit limbs, most significant first.
+                buf.push(*i >> 32);
+                buf.push(*i as u32 as u64);
+            }
 
-                Value::Hash(hash) => {
-                    let start = buf.len();
-                    buf.resize(start + 4, 0);
+            Value::U256(num) => {
+                let start = buf.len();
+                buf.resize(start + 8, 0);
 
-                    // big-endian, as if it were a uint160.
-                    buf[start..(start + 4)].copy_from_slice(&hash.0);
-                }
+                // big-endian, as if it were a uint160.
+                buf[start..(start + 8)].copy_from_slice(&num.0);
+            }
 
-                Value::Bool(b) => {
-                    let start = buf.len();
-                    buf.resize(start + 1, 0);
+            Value::I32(i) => {
+                // two's complement in the low 32 bits.
+                buf.push(*i as u32 as u64);
+            }
 
-                    if *b {
-                        buf[start] = 1;
-                    }
-                }
+            Value::I64(i) => {
+                // two's complement as two 32-bit limbs, most significant first.
+                buf.push((*i as u64) >> 32);
+                buf.push(*i as u32 as u64);
+            }
 
-                Value::FixedArray(values, _) => {
-                    // write array values
-                    let bytes = Self::encode(values);
-                    buf.extend(bytes);
-                }
+            Value::Field(i) => {
+                let start = buf.len();
+                buf.resize(start + 1, *i);
+            }
 
-                Value::Tuple(values) => {
-                    let values: Vec<_> = values.iter().cloned().map(|(_, value)| value).collect();
+            Value::Address(addr) => {
+                let start = buf.len();
+                buf.resize(start + 4, 0);
 
-                    let bytes = Self::encode(&values);
-                    buf.extend(bytes);
+                // big-endian, as if it were a uint160.
+                buf[start..(start + 4)].copy_from_slice(&addr.0);
+            }
+
+            Value::Hash(hash) => {
+                let start = buf.len();
+                buf.resize(start + 4, 0);
+
+                // big-endian, as if it were a uint160.
+                buf[start..(start + 4)].copy_from_slice(&hash.0);
+            }
+
+            Value::Bool(b) => {
+                let start = buf.len();
+                buf.resize(start + 1, 0);
+
+                if *b {
+                    buf[start] = 1;
                 }
+            }
+
+            Value::Enum(variant, ty) => {
+                let index = match ty {
+                    Type::Enum { name, variants } => variants
+                        .iter()
+                        .position(|v| v == variant)
+                        .ok_or_else(|| AbiError::UnknownVariant {
+                            path: path.to_string(),
+                            name: name.clone(),
+                            variant: variant.clone(),
+                        })?,
+                    _ => {
+                        return Err(AbiError::InvalidValue {
+                            path: path.to_string(),
+                            ty: Box::new(ty.clone()),
+                            input: variant.clone(),
+                        })
+                    }
+                };
+                buf.push(index as u64);
+            }
+
+            Value::FixedBytes(bytes) => buf.extend(bytes.iter().map(|b| *b as u64)),
 
-                Value::String(value) => {
-                    let start = buf.len();
-                    let value_len = value.as_bytes().len();
-                    let new_len = start + value_len + 1;
-                    buf.resize(new_len, value_len as u64);
-
-                    // TODO Currently, Ola can only encode strings into arrays based on fields
-                    // and does not support encoding into u8 type arrays.
-                    // write bytes
-                    buf[start + 1..(new_len)].copy_from_slice(
-                        value
-                            .as_bytes()
-                            .into_iter()
-                            .map(|x| *x as u64)
-                            .collect::<Vec<u64>>()
-                            .as_slice(),
-                    );
+            Value::FixedArray(values, _) => {
+                // write array values
+                for (i, value) in values.iter().enumerate() {
+                    value.encode_into(buf, &format!("{}[{}]", path, i))?;
                 }
+            }
 
-                Value::Fields(value) => {
-                    let start = buf.len();
-                    let value_len = value.len();
-                    let new_len = start + value_len + 1;
-                    buf.resize(new_len, value_len as u64);
+            Value::Tuple(values) => {
+                for (i, (name, value)) in values.iter().enumerate() {
+                    let field = if name.is_empty() {
+                        i.to_string()
+                    } else {
+                        name.clone()
+                    };
 
-                    // write bytes
-                    buf[start + 1..new_len].copy_from_slice(value);
+                    value.encode_into(buf, &format!("{}.{}", path, field))?;
                 }
+            }
+
+            Value::String(value) => {
+                let start = buf.len();
+                let value_len = value.len();
+                let new_len = start + value_len + 1;
+                buf.resize(new_len, value_len as u64);
+
+                // TODO Currently, Ola can only encode strings into arrays based on fields
+                // and does not support encoding into u8 type arrays.
+                // write bytes
+                buf[start + 1..(new_len)].copy_from_slice(
+                    value
+                        .as_bytes()
+                        .iter()
+                        .map(|x| *x as u64)
+                        .collect::<Vec<u64>>()
+                        .as_slice(),
+                );
+            }
+
+            Value::Fields(value) => {
+                let start = buf.len();
+                let value_len = value.len();
+                let new_len = start + value_len + 1;
+                buf.resize(new_len, value_len as u64);
 
-                Value::Array(values, _) => {
-                    let start = buf.len();
-                    buf.resize(start + 1, values.len() as u64);
-                    // write array values
-                    let bytes = Self::encode(values);
-                    buf.extend(bytes);
+                // write bytes
+                buf[start + 1..new_len].copy_from_slice(value);
+            }
+
+            Value::Array(values, _) => {
+                let start = buf.len();
+                buf.resize(start + 1, values.len() as u64);
+                // write array values
+                for (i, value) in values.iter().enumerate() {
+                    value.encode_into(buf, &format!("{}[{}]", path, i))?;
                 }
-            };
+            }
         }
 
-        buf
+        Ok(())
+    }
+
+    /// Encodes the value into a log topics entry, as the VM does for indexed event params.
+    ///
+    /// Value types are stored right-aligned in the topic, the way
+    /// [`Event::decode_data_from_slice`](crate::Event::decode_data_from_slice) reads
+    /// them back, and `address` and `hash` values are stored as-is. Strings are hashed
+    /// with Poseidon over their UTF-8 bytes, zero-padded to a multiple of 8 bytes,
+    /// which reproduces the `BookCreated` log emitted by the VM in
+    /// `examples/log_data.rs`; `bytesN` values are hashed the same way.
+    ///
+    /// Other values are hashed with Poseidon over their encoded words: the eight
+    /// limbs of a `u256`, the fields of a tuple or struct one after another, and the
+    /// elements of `fields` and arrays without the leading length word, just as a
+    /// string's length is left out of its hash.
+    pub fn encode_topic(&self) -> Result<FixedArray4> {
+        match self {
+            Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_)
+            | Value::U64(_)
+            | Value::I32(_)
+            | Value::I64(_)
+            | Value::Field(_)
+            | Value::Bool(_)
+            | Value::Enum(_, _) => {
+                // right-aligned words, big-endian.
+                let mut words = vec![];
+                self.encode_into(&mut words, "$")?;
+                let mut topic = [0u64; 4];
+                topic[4 - words.len()..].copy_from_slice(&words);
+                Ok(FixedArray4(topic))
+            }
+            Value::Address(v) | Value::Hash(v) => Ok(*v),
+            Value::String(s) => Ok(FixedArray4(unsafe_poseidon_bytes_auto_padded(s.as_bytes()))),
+            Value::FixedBytes(bytes) => Ok(FixedArray4(unsafe_poseidon_bytes_auto_padded(bytes))),
+            Value::Fields(words) => Ok(FixedArray4(poseidon_u64(words))),
+            Value::Array(values, _) | Value::FixedArray(values, _) => {
+                let mut words = vec![];
+                for (i, value) in values.iter().enumerate() {
+                    value.encode_into(&mut words, &format!("$[{}]", i))?;
+                }
+                Ok(FixedArray4(poseidon_u64(&words)))
+            }
+            Value::U256(_) | Value::Tuple(_) => {
+                let mut words = vec![];
+                self.encode_into(&mut words, "$")?;
+                Ok(FixedArray4(poseidon_u64(&words)))
+            }
+        }
     }
 
     /// Returns the type of the given value.
     pub fn type_of(&self) -> Type {
         match self {
+            Value::U8(_) => Type::U8,
+            Value::U16(_) => Type::U16,
             Value::U32(_) => Type::U32,
+            Value::U64(_) => Type::U64,
             Value::U256(_) => Type::U256,
+            Value::I32(_) => Type::I32,
+            Value::I64(_) => Type::I64,
             Value::Field(_) => Type::Field,
             Value::Address(_) => Type::Address,
             Value::Hash(_) => Type::Hash,
             Value::Bool(_) => Type::Bool,
+            Value::FixedBytes(bytes) => Type::FixedBytes(bytes.len()),
+            Value::Enum(_, ty) => ty.clone(),
             Value::FixedArray(values, ty) => {
                 Type::FixedArray(Box::new(ty.clone()), values.len() as u64)
             }
@@ -249,15 +794,165 @@ impl Value {
         }
     }
 
-    fn decode(bs: &[u64], ty: &Type, base_addr: usize, at: usize) -> Result<(Value, usize)> {
+    /// Checks that the value conforms to the given type.
+    ///
+    /// `path` names the value being checked (e.g. `inputs[1]`) and is extended
+    /// with field names and array indexes to locate the offending value, so an
+    /// error reads like `type mismatch at inputs[1].x[3]: expected u32, found field`.
+    ///
+    /// Tuple values with an empty field name are matched by position only, and
+    /// the element type stored in arrays must have the declared layout.
+    pub fn type_check(&self, ty: &Type, path: &str) -> Result<()> {
+        let mismatch = || AbiError::TypeMismatch {
+            path: path.to_string(),
+            expected: ty.clone(),
+            found: self.type_of(),
+        };
+
+        match (self, ty) {
+            (Value::FixedArray(values, value_elem_ty), Type::FixedArray(elem_ty, size)) => {
+                if !same_layout(value_elem_ty, elem_ty) {
+                    return Err(mismatch());
+                }
+
+                if values.len() as u64 != *size {
+                    return Err(AbiError::LengthMismatch {
+                        path: path.to_string(),
+                        expected: *size as usize,
+                        found: values.len(),
+                    });
+                }
+
+                values.iter().enumerate().try_for_each(|(i, value)| {
+                    value.type_check(elem_ty, &format!("{}[{}]", path, i))
+                })
+            }
+
+            (Value::Array(values, value_elem_ty), Type::Array(elem_ty)) => {
+                if !same_layout(value_elem_ty, elem_ty) {
+                    return Err(mismatch());
+                }
+
+                values.iter().enumerate().try_for_each(|(i, value)| {
+                    value.type_check(elem_ty, &format!("{}[{}]", path, i))
+                })
+            }
+
+            (Value::Enum(variant, value_ty), Type::Enum { name, variants }) if value_ty == ty => {
+                if variants.contains(variant) {
+                    Ok(())
+                } else {
+                    Err(AbiError::UnknownVariant {
+                        path: path.to_string(),
+                        name: name.clone(),
+                        variant: variant.clone(),
+                    })
+                }
+            }
+
+            (Value::Tuple(values), Type::Tuple(tys) | Type::Struct { fields: tys, .. }) => {
+                if values.len() != tys.len() {
+                    return Err(AbiError::LengthMismatch {
+                        path: path.to_string(),
+                        expected: tys.len(),
+                        found: values.len(),
+                    });
+                }
+
+                values.iter().zip(tys).enumerate().try_for_each(
+                    |(i, ((name, value), (ty_name, ty)))| {
+                        if !name.is_empty() && name != ty_name {
+                            return Err(AbiError::FieldMismatch {
+                                path: path.to_string(),
+                                expected: ty_name.clone(),
+                                found: name.clone(),
+                            });
+                        }
+
+                        let field = if ty_name.is_empty() {
+                            i.to_string()
+                        } else {
+                            ty_name.clone()
+                        };
+
+                        value.type_check(ty, &format!("{}.{}", path, field))
+                    },
+                )
+            }
+
+            _ => {
+                if self.type_of() == *ty {
+                    Ok(())
+                } else {
+                    Err(mismatch())
+                }
+            }
+        }
+    }
+
+    fn decode(
+        bs: &[u64],
+        ty: &Type,
+        base_addr: usize,
+        at: usize,
+        opts: &DecodeOptions,
+    ) -> Result<(Value, usize)> {
         match ty {
+            Type::U8 => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 1))
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
+
+                opts.check(ty, at, slice[0], u8::MAX as u64)?;
+
+                Ok((Value::U8(slice[0] as u8), 1))
+            }
+
+            Type::U16 => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 1))
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
+
+                opts.check(ty, at, slice[0], u16::MAX as u64)?;
+
+                Ok((Value::U16(slice[0] as u16), 1))
+            }
+
+            Type::U64 => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 2))
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
+
+                opts.check(ty, at, slice[0], u32::MAX as u64)?;
+                opts.check(ty, at + 1, slice[1], u32::MAX as u64)?;
+                let u64_value = (slice[0] as u32 as u64) << 32 | slice[1] as u32 as u64;
+
+                Ok((Value::U64(u64_value), 2))
+            }
+
             Type::U32 => {
                 let at = base_addr + at;
                 let slice = bs
                     .get(at..(at + 1))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding {:?}", ty))?;
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
 
                 let u32_value = slice[0];
+                opts.check(ty, at, u32_value, u32::MAX as u64)?;
 
                 Ok((Value::U32(u32_value), 1))
             }
@@ -266,21 +961,61 @@ impl Value {
                 let at = base_addr + at;
                 let slice = bs
                     .get(at..(at + 8))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding {:?}", ty))?;
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
 
                 let mut u256_value = [0u64; 8];
                 u256_value.copy_from_slice(slice);
+                for (i, limb) in u256_value.iter().enumerate() {
+                    opts.check(ty, at + i, *limb, u32::MAX as u64)?;
+                }
 
                 Ok((Value::U256(FixedArray8(u256_value)), 8))
             }
 
+            Type::I32 => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 1))
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
+
+                opts.check(ty, at, slice[0], u32::MAX as u64)?;
+
+                Ok((Value::I32(slice[0] as u32 as i32), 1))
+            }
+
+            Type::I64 => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 2))
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
+
+                opts.check(ty, at, slice[0], u32::MAX as u64)?;
+                opts.check(ty, at + 1, slice[1], u32::MAX as u64)?;
+                let i64_value = ((slice[0] as u32 as u64) << 32 | slice[1] as u32 as u64) as i64;
+
+                Ok((Value::I64(i64_value), 2))
+            }
+
             Type::Field => {
                 let at = base_addr + at;
                 let slice = bs
                     .get(at..(at + 1))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding {:?}", ty))?;
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
 
                 let field_value = slice[0];
+                opts.check(ty, at, field_value, FIELD_ORDER - 1)?;
 
                 Ok((Value::Field(field_value), 1))
             }
@@ -289,7 +1024,10 @@ impl Value {
                 let at = base_addr + at;
                 let slice = bs
                     .get(at..(at + 4))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding {:?}", ty))?;
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
 
                 let mut addr = [0u64; 4];
                 addr.copy_from_slice(slice);
@@ -301,7 +1039,10 @@ impl Value {
                 let at = base_addr + at;
                 let slice = bs
                     .get(at..(at + 4))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding {:?}", ty))?;
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
 
                 let mut hash = [0u64; 4];
                 hash.copy_from_slice(slice);
@@ -313,15 +1054,60 @@ impl Value {
                 let at = base_addr + at;
                 let slice = bs
                     .get(at..(at + 1))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding bool"))?;
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
 
+                opts.check(ty, at, slice[0], 1)?;
                 let b = slice[0] == 1;
 
                 Ok((Value::Bool(b), 1))
             }
+
+            Type::Enum { name, variants } => {
+                let at = base_addr + at;
+                let slice = bs
+                    .get(at..(at + 1))
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    })?;
+
+                let variant = variants.get(slice[0] as usize).ok_or_else(|| {
+                    AbiError::InvalidDiscriminant {
+                        name: name.clone(),
+                        offset: at,
+                        discriminant: slice[0],
+                    }
+                })?;
+
+                Ok((Value::Enum(variant.clone(), ty.clone()), 1))
+            }
+
+            Type::FixedBytes(size) => {
+                let at = base_addr + at;
+                let slice = bs.get(at..at.saturating_add(*size)).ok_or_else(|| {
+                    AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at,
+                    }
+                })?;
+
+                for (i, byte) in slice.iter().enumerate() {
+                    opts.check(ty, at + i, *byte, u8::MAX as u64)?;
+                }
+
+                Ok((
+                    Value::FixedBytes(slice.iter().map(|b| *b as u8).collect()),
+                    *size,
+                ))
+            }
+
             Type::FixedArray(ty, size) => (0..(*size))
                 .try_fold((vec![], 0), |(mut values, total_consumed), _| {
-                    let (value, consumed) = Self::decode(bs, ty, base_addr, at + total_consumed)?;
+                    let (value, consumed) =
+                        Self::decode(bs, ty, base_addr, at + total_consumed, opts)?;
 
                     values.push(value);
 
@@ -330,7 +1116,14 @@ impl Value {
                 .map(|(values, consumed)| (Value::FixedArray(values, *ty.clone()), consumed)),
 
             Type::String => {
-                let (bytes_value, consumed) = Self::decode(bs, &Type::Fields, base_addr, at)?;
+                let (bytes_value, consumed) = Self::decode(bs, &Type::Fields, base_addr, at, opts)
+                    .map_err(|err| match err {
+                        AbiError::UnexpectedEof { offset, .. } => AbiError::UnexpectedEof {
+                            ty: Type::String,
+                            offset,
+                        },
+                        err => err,
+                    })?;
 
                 let bytes = if let Value::Fields(bytes) = bytes_value {
                     bytes
@@ -339,24 +1132,42 @@ impl Value {
                     unreachable!();
                 };
 
-                let s = String::from_utf8(bytes.into_iter().map(|b| b as u8).collect())?;
+                for (i, byte) in bytes.iter().enumerate() {
+                    opts.check(ty, base_addr + at + 1 + i, *byte, u8::MAX as u64)?;
+                }
+
+                let s = String::from_utf8(bytes.into_iter().map(|b| b as u8).collect()).map_err(
+                    |_| AbiError::InvalidUtf8 {
+                        offset: base_addr + at,
+                    },
+                )?;
 
                 Ok((Value::String(s), consumed))
             }
 
             Type::Fields => {
                 let at = base_addr + at;
-                let field_len_slice = bs
-                    .get(at..(at + 1))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding fields length"))?;
+                let field_len_slice =
+                    bs.get(at..(at + 1))
+                        .ok_or_else(|| AbiError::UnexpectedEof {
+                            ty: ty.clone(),
+                            offset: at,
+                        })?;
                 let field_len = field_len_slice[0] as usize;
 
                 let at = at + 1;
                 let fields_value = bs
-                    .get(at..(at + field_len))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding bytes"))?
+                    .get(at..at.saturating_add(field_len))
+                    .ok_or_else(|| AbiError::UnexpectedEof {
+                        ty: ty.clone(),
+                        offset: at - 1,
+                    })?
                     .to_vec();
 
+                for (i, field) in fields_value.iter().enumerate() {
+                    opts.check(ty, at + i, *field, FIELD_ORDER - 1)?;
+                }
+
                 // consumes only the first 32 bytes, i.e. the offset pointer
                 Ok((Value::Fields(fields_value), field_len + 1))
             }
@@ -364,16 +1175,19 @@ impl Value {
             Type::Array(ty) => {
                 let at = base_addr + at;
 
-                let array_len_slice = bs
-                    .get(at..(at + 1))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding array length"))?;
+                let array_len_slice =
+                    bs.get(at..(at + 1))
+                        .ok_or_else(|| AbiError::UnexpectedEof {
+                            ty: Type::Array(ty.clone()),
+                            offset: at,
+                        })?;
                 let array_len = array_len_slice[0];
 
                 let at = at + 1;
 
                 (0..array_len)
                     .try_fold((vec![], 0), |(mut values, total_consumed), _| {
-                        let (value, consumed) = Self::decode(bs, ty, at, total_consumed)?;
+                        let (value, consumed) = Self::decode(bs, ty, at, total_consumed, opts)?;
                         values.push(value);
 
                         Ok((values, total_consumed + consumed))
@@ -383,27 +1197,314 @@ impl Value {
                     })
             }
 
-            Type::Tuple(tys) => tys
+            Type::Tuple(tys) | Type::Struct { fields: tys, .. } => tys
                 .iter()
                 .cloned()
                 .try_fold((vec![], 0), |(mut values, total_consumed), (name, ty)| {
-                    let (value, consumed) = Self::decode(bs, &ty, base_addr, at + total_consumed)?;
+                    let (value, consumed) =
+                        Self::decode(bs, &ty, base_addr, at + total_consumed, opts)?;
+
+                    values.push((name, value));
+
+                    Ok((values, total_consumed + consumed))
+                })
+                .map(|(values, total_consumed)| (Value::Tuple(values), total_consumed)),
+        }
+    }
+}
+
+/// Formats values on one line the way [`Value::parse`] reads them, e.g.
+/// `[(1, "a"), (2, "b")]`: integers, fields and `u256` in decimal, addresses,
+/// hashes and `bytesN` in hex, strings quoted, enums by variant name, arrays as
+/// `[..]` and tuples as `(..)`.
+///
+/// The alternate form `{:#}` names tuple fields and lays out composites holding
+/// composites over several lines, indenting their items:
+///
+/// ```text
+/// [
+///     (id: 1, name: "a"),
+///     (id: 2, name: "b"),
+/// ]
+/// ```
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return self.write_pretty(f, 0);
+        }
+
+        match self {
+            Value::U8(i) => write!(f, "{}", i),
+            Value::U16(i) => write!(f, "{}", i),
+            Value::U32(i) | Value::U64(i) | Value::Field(i) => write!(f, "{}", i),
+            Value::I32(i) => write!(f, "{}", i),
+            Value::I64(i) => write!(f, "{}", i),
+            Value::U256(num) => write!(f, "{}", num.to_dec_string()),
+            Value::Address(v) | Value::Hash(v) => write!(f, "{}", v),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::FixedBytes(bytes) => {
+                write!(f, "0x")?;
+                for b in bytes {
+                    write!(f, "{:02x}", b)?;
+                }
+                Ok(())
+            }
+            Value::String(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Value::Enum(variant, _) => write!(f, "{}", variant),
+            Value::Fields(fields) => write_list(f, "[", fields, "]"),
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                write_list(f, "[", values, "]")
+            }
+            Value::Tuple(fields) => write_list(f, "(", fields.iter().map(|(_, value)| value), ")"),
+        }
+    }
+}
+
+impl Value {
+    /// Writes the alternate form of the value, nested `depth` levels deep.
+    fn write_pretty(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let (open, items, close): (_, Vec<_>, _) = match self {
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                ("[", values.iter().map(|value| ("", value)).collect(), "]")
+            }
+            Value::Tuple(fields) => (
+                "(",
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value))
+                    .collect(),
+                ")",
+            ),
+            value => return write!(f, "{}", value),
+        };
+
+        let nested = items.iter().any(|(_, value)| value.is_composite());
+
+        write!(f, "{}", open)?;
+        for (i, (name, value)) in items.iter().enumerate() {
+            if nested {
+                write!(f, "\n{:width$}", "", width = (depth + 1) * 4)?;
+            } else if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            if !name.is_empty() {
+                write!(f, "{}: ", name)?;
+            }
+            value.write_pretty(f, depth + 1)?;
+
+            if nested {
+                write!(f, ",")?;
+            }
+        }
+        if nested {
+            write!(f, "\n{:width$}", "", width = depth * 4)?;
+        }
+        write!(f, "{}", close)
+    }
+
+    /// Returns whether the value holds other values.
+    fn is_composite(&self) -> bool {
+        matches!(
+            self,
+            Value::FixedArray(..) | Value::Array(..) | Value::Tuple(_)
+        )
+    }
+}
+
+fn write_list<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    open: &str,
+    items: impl IntoIterator<Item = T>,
+    close: &str,
+) -> fmt::Result {
+    write!(f, "{}", open)?;
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    write!(f, "{}", close)
+}
+
+/// Returns whether values of both types share the same layout: tuples and
+/// structs match on their field types, ignoring field and struct names.
+fn same_layout(a: &Type, b: &Type) -> bool {
+    match (a, b) {
+        (
+            Type::Tuple(a) | Type::Struct { fields: a, .. },
+            Type::Tuple(b) | Type::Struct { fields: b, .. },
+        ) => a.len() == b.len() && a.iter().zip(b).all(|((_, a), (_, b))| same_layout(a, b)),
+        (Type::Array(a), Type::Array(b)) => same_layout(a, b),
+        (Type::FixedArray(a, n), Type::FixedArray(b, m)) => n == m && same_layout(a, b),
+        (a, b) => a == b,
+    }
+}
+
+impl From<GoldilocksField> for Value {
+    fn from(value: GoldilocksField) -> Self {
+        Value::Field(value.to_canonical_u64())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_fixed_array_hex() {
+        assert_eq!(
+            "0x0000000100000000000000020000000000000003"
+                .parse::<FixedArray4>()
+                .unwrap(),
+            FixedArray4([0, 1, 2, 3])
+        );
+        assert_eq!(
+            FixedArray8::try_from("abc").unwrap(),
+            FixedArray8([0, 0, 0, 0, 0, 0, 0, 0xabc])
+        );
+        assert_eq!(
+            "0x12z4".parse::<FixedArray4>().unwrap_err(),
+            FromHexError::InvalidChar { c: 'z', index: 4 }
+        );
+        assert_eq!(
+            format!("0x{}", "f".repeat(65))
+                .parse::<FixedArray8>()
+                .unwrap_err(),
+            FromHexError::TooLong { max: 64, found: 65 }
+        );
+        assert_eq!(
+            FixedArray4::from_hex_exact("0x01").unwrap_err(),
+            FromHexError::InvalidLength {
+                expected: 64,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn fixed_array_bytes() {
+        let mut bytes = [0u8; 32];
+        bytes[7] = 1;
+        bytes[31] = 4;
+
+        let arr4 = FixedArray4::from_bytes(&bytes);
+        assert_eq!(arr4, FixedArray4([1, 0, 0, 4]));
+        assert_eq!(arr4.to_bytes(), bytes);
+
+        let arr8 = FixedArray8::from_bytes(&bytes);
+        assert_eq!(arr8, FixedArray8([0, 1, 0, 0, 0, 0, 0, 4]));
+        assert_eq!(arr8.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn u256_conversions() {
+        let num = FixedArray8::from(u128::MAX);
+        assert_eq!(
+            num,
+            FixedArray8([0, 0, 0, 0, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff])
+        );
+        assert_eq!(num.to_u128(), Some(u128::MAX));
+        assert_eq!(num.to_u64(), None);
+        assert_eq!(FixedArray8::from(42u64).to_u64(), Some(42));
+        assert_eq!(FixedArray8::MAX.to_u128(), None);
+
+        let mut le = [0u8; 32];
+        le[0] = 1;
+        le[31] = 2;
+        let from_le = FixedArray8::from_le_bytes(&le);
+        assert_eq!(from_le, FixedArray8([0x02000000, 0, 0, 0, 0, 0, 0, 1]));
+        assert_eq!(from_le.to_le_bytes(), le);
+        assert_eq!(FixedArray8::from_be_bytes(&from_le.to_be_bytes()), from_le);
+
+        assert_eq!(
+            FixedArray8::from_limbs([0, 0, 1, 0, 0, 0, 0, 0]).unwrap(),
+            FixedArray8([0, 0, 1, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            FixedArray8::from_limbs([0, 0, 1 << 32, 0, 0, 0, 0, 0]).unwrap_err(),
+            AbiError::InvalidLimb {
+                index: 2,
+                limb: 1 << 32
+            }
+        );
+    }
 
-                    values.push((name, value));
+    #[test]
+    fn u256_decimal() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(FixedArray8::from_dec_str(max).unwrap(), FixedArray8::MAX);
+        assert_eq!(FixedArray8::MAX.to_dec_string(), max);
+        assert_eq!(FixedArray8::ZERO.to_dec_string(), "0");
+        assert_eq!(
+            FixedArray8::from_dec_str("1000000000000000000").unwrap(),
+            FixedArray8::from(1_000_000_000_000_000_000u64)
+        );
+        assert_eq!(
+            FixedArray8::from(1_000_000_000_000_000_007u64).to_dec_string(),
+            "1000000000000000007"
+        );
 
-                    Ok((values, total_consumed + consumed))
-                })
-                .map(|(values, total_consumed)| (Value::Tuple(values), total_consumed)),
-        }
+        assert_eq!(FixedArray8::from_dec_str(""), Err(FromDecError::Empty));
+        assert_eq!(
+            FixedArray8::from_dec_str("12a"),
+            Err(FromDecError::InvalidChar { c: 'a', index: 2 })
+        );
+        assert_eq!(
+            FixedArray8::from_dec_str(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+            ),
+            Err(FromDecError::Overflow)
+        );
     }
-}
 
-#[cfg(test)]
-mod test {
+    #[test]
+    fn u256_arithmetic() {
+        let a = FixedArray8::from(u64::MAX);
+        let b = FixedArray8::from(2u64);
 
-    use super::*;
+        assert_eq!(
+            a.checked_add(&b),
+            Some(FixedArray8::from(u64::MAX as u128 + 2))
+        );
+        assert_eq!(FixedArray8::MAX.checked_add(&FixedArray8::from(1u64)), None);
+        assert_eq!(a.checked_sub(&b), Some(FixedArray8::from(u64::MAX - 2)));
+        assert_eq!(b.checked_sub(&a), None);
+        assert_eq!(
+            FixedArray8::ZERO.checked_sub(&FixedArray8::ZERO),
+            Some(FixedArray8::ZERO)
+        );
+        assert_eq!(
+            a.checked_mul(&a),
+            Some(FixedArray8::from(u64::MAX as u128 * u64::MAX as u128))
+        );
+        assert_eq!(FixedArray8::MAX.checked_mul(&b), None);
+        assert_eq!(
+            FixedArray8::MAX.checked_mul(&FixedArray8::from(1u64)),
+            Some(FixedArray8::MAX)
+        );
 
-    use pretty_assertions::assert_eq;
+        assert!(a > b);
+        assert!(FixedArray8::MAX > a);
+        assert!(FixedArray8::ZERO.is_zero());
+        assert!(!b.is_zero());
+    }
 
     #[test]
     fn decode_uint() {
@@ -417,13 +1518,15 @@ mod test {
 
     #[test]
     fn decode_u256() {
-        let bs = FixedArray8::from("0x0a");
+        let bs = "0x0a".parse::<FixedArray8>().unwrap();
 
         let v = Value::decode_from_slice(&bs.0, &[Type::U256]).expect("decode_from_slice failed");
 
         assert_eq!(v, vec![Value::U256(FixedArray8([0, 0, 0, 0, 0, 0, 0, 10]))]);
-        let bs =
-            FixedArray8::from("0x000000010000000200000003000000040000000500000006000000070000000a");
+        let bs = FixedArray8::from_hex_exact(
+            "0x000000010000000200000003000000040000000500000006000000070000000a",
+        )
+        .unwrap();
 
         let v = Value::decode_from_slice(&bs.0, &[Type::U256]).expect("decode_from_slice failed");
 
@@ -442,16 +1545,188 @@ mod test {
             vec![Value::Field(100), Value::Field(200), Value::Field(300)]
         );
     }
+
+    #[test]
+    fn decode_sized() {
+        let bs = vec![0xff, 0xffff, 1, 2, 0x61, 0x62];
+
+        let v =
+            Value::decode_from_slice(&bs, &[Type::U8, Type::U16, Type::U64, Type::FixedBytes(2)])
+                .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![
+                Value::U8(0xff),
+                Value::U16(0xffff),
+                Value::U64(1 << 32 | 2),
+                Value::FixedBytes(vec![0x61, 0x62])
+            ]
+        );
+        assert_eq!(Value::encode(&v).unwrap(), bs);
+
+        let strict = DecodeOptions::strict();
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[0x100], &[Type::U8], &strict).unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::U8,
+                offset: 0,
+                word: 0x100
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[0x10000], &[Type::U16], &strict).unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::U16,
+                offset: 0,
+                word: 0x10000
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[0x61, 0x162], &[Type::FixedBytes(2)], &strict)
+                .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::FixedBytes(2),
+                offset: 1,
+                word: 0x162
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice(&[0x61], &[Type::FixedBytes(2)]).unwrap_err(),
+            AbiError::UnexpectedEof {
+                ty: Type::FixedBytes(2),
+                offset: 0
+            }
+        );
+        assert_eq!(
+            Value::FixedBytes(vec![1])
+                .type_check(&Type::FixedBytes(2), "x")
+                .unwrap_err(),
+            AbiError::TypeMismatch {
+                path: "x".to_string(),
+                expected: Type::FixedBytes(2),
+                found: Type::FixedBytes(1)
+            }
+        );
+    }
+
+    #[test]
+    fn decode_enum() {
+        let color = Type::Enum {
+            name: "Color".to_string(),
+            variants: vec!["Red".to_string(), "Green".to_string()],
+        };
+
+        let v = Value::decode_from_slice(&[1, 0], &[color.clone(), color.clone()])
+            .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![
+                Value::Enum("Green".to_string(), color.clone()),
+                Value::Enum("Red".to_string(), color.clone())
+            ]
+        );
+        assert_eq!(Value::encode(&v).unwrap(), vec![1, 0]);
+        assert_eq!(v[0].type_check(&color, "x"), Ok(()));
+
+        assert_eq!(
+            Value::decode_from_slice(&[1, 2], &[color.clone(), color.clone()]).unwrap_err(),
+            AbiError::InvalidDiscriminant {
+                name: "Color".to_string(),
+                offset: 1,
+                discriminant: 2
+            }
+        );
+        assert_eq!(
+            Value::Enum("Blue".to_string(), color.clone())
+                .type_check(&color, "x")
+                .unwrap_err(),
+            AbiError::UnknownVariant {
+                path: "x".to_string(),
+                name: "Color".to_string(),
+                variant: "Blue".to_string()
+            }
+        );
+        assert_eq!(
+            Value::U32(1).type_check(&color, "x").unwrap_err(),
+            AbiError::TypeMismatch {
+                path: "x".to_string(),
+                expected: color.clone(),
+                found: Type::U32
+            }
+        );
+        assert_eq!(
+            Value::U32(1)
+                .type_check(&color, "x")
+                .unwrap_err()
+                .to_string(),
+            "type mismatch at x: expected Color, found u32"
+        );
+
+        assert_eq!(
+            Value::encode(&[
+                Value::U32(1),
+                Value::Tuple(vec![(
+                    "color".to_string(),
+                    Value::Enum("Blue".to_string(), color.clone())
+                )])
+            ])
+            .unwrap_err(),
+            AbiError::UnknownVariant {
+                path: "$[1].color".to_string(),
+                name: "Color".to_string(),
+                variant: "Blue".to_string()
+            }
+        );
+        assert_eq!(
+            Value::encode(&[Value::Enum("Red".to_string(), Type::U32)]).unwrap_err(),
+            AbiError::InvalidValue {
+                path: "$[0]".to_string(),
+                ty: Box::new(Type::U32),
+                input: "Red".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn decode_int() {
+        let bs = vec![0xffffffff, 7, 0xffffffff, 0xfffffffe, 0, 3];
+
+        let v = Value::decode_from_slice(&bs, &[Type::I32, Type::I32, Type::I64, Type::I64])
+            .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![Value::I32(-1), Value::I32(7), Value::I64(-2), Value::I64(3)]
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(
+                &[1 << 32],
+                &[Type::I32],
+                &DecodeOptions::strict()
+            )
+            .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::I32,
+                offset: 0,
+                word: 1 << 32
+            }
+        );
+    }
+
     #[test]
     fn decode_address() {
-        let bs = FixedArray4::from("0x000000020000000000000003");
+        let bs = "0x000000020000000000000003".parse::<FixedArray4>().unwrap();
 
         let v =
             Value::decode_from_slice(&bs.0, &[Type::Address]).expect("decode_from_slice failed");
 
         assert_eq!(v, vec![Value::Address(FixedArray4([0, 0, 2, 3]))]);
-        let bs =
-            FixedArray4::from("0x0000000000000000000000000000000100000000000000020000000000000003");
+        let bs = FixedArray4::from_hex_exact(
+            "0x0000000000000000000000000000000100000000000000020000000000000003",
+        )
+        .unwrap();
 
         let v =
             Value::decode_from_slice(&bs.0, &[Type::Address]).expect("decode_from_slice failed");
@@ -508,7 +1783,7 @@ mod test {
     fn decode_string() {
         let source = "olavm"
             .as_bytes()
-            .into_iter()
+            .iter()
             .map(|x| *x as u64)
             .collect::<Vec<u64>>();
         let mut bs = vec![source.len() as u64];
@@ -523,7 +1798,7 @@ mod test {
     fn decode_fields() {
         let source = "hello,world"
             .as_bytes()
-            .into_iter()
+            .iter()
             .map(|x| *x as u64)
             .collect::<Vec<u64>>();
         let mut bs = vec![source.len() as u64];
@@ -628,7 +1903,7 @@ mod test {
         let str = "olavm".to_string();
         let source = str
             .as_bytes()
-            .into_iter()
+            .iter()
             .map(|x| *x as u64)
             .collect::<Vec<u64>>();
         bs.resize(2, 0);
@@ -687,13 +1962,375 @@ mod test {
         );
     }
 
+    #[test]
+    fn type_check_tuple() {
+        let ty = Type::Tuple(vec![
+            ("a".to_string(), Type::U32),
+            (
+                "x".to_string(),
+                Type::Array(Box::new(Type::FixedArray(Box::new(Type::Bool), 2))),
+            ),
+        ]);
+
+        let value = Value::Tuple(vec![
+            ("a".to_string(), Value::U32(1)),
+            (
+                "x".to_string(),
+                Value::Array(
+                    vec![Value::FixedArray(
+                        vec![Value::Bool(true), Value::Bool(false)],
+                        Type::Bool,
+                    )],
+                    Type::FixedArray(Box::new(Type::Bool), 2),
+                ),
+            ),
+        ]);
+        value
+            .type_check(&ty, "inputs[0]")
+            .expect("type_check failed");
+
+        let unnamed = Value::Tuple(vec![
+            ("".to_string(), Value::U32(1)),
+            (
+                "".to_string(),
+                Value::Array(vec![], Type::FixedArray(Box::new(Type::Bool), 2)),
+            ),
+        ]);
+        unnamed
+            .type_check(&ty, "inputs[0]")
+            .expect("type_check failed");
+
+        let bad_element_type = Value::Tuple(vec![
+            ("a".to_string(), Value::U32(1)),
+            ("x".to_string(), Value::Array(vec![], Type::Bool)),
+        ]);
+        assert_eq!(
+            bad_element_type.type_check(&ty, "inputs[0]").unwrap_err(),
+            AbiError::TypeMismatch {
+                path: "inputs[0].x".to_string(),
+                expected: Type::Array(Box::new(Type::FixedArray(Box::new(Type::Bool), 2))),
+                found: Type::Array(Box::new(Type::Bool))
+            }
+        );
+
+        let swapped = Value::Tuple(vec![
+            ("x".to_string(), Value::U32(1)),
+            (
+                "a".to_string(),
+                Value::Array(vec![], Type::FixedArray(Box::new(Type::Bool), 2)),
+            ),
+        ]);
+        assert_eq!(
+            swapped.type_check(&ty, "inputs[0]").unwrap_err(),
+            AbiError::FieldMismatch {
+                path: "inputs[0]".to_string(),
+                expected: "a".to_string(),
+                found: "x".to_string()
+            }
+        );
+
+        let bad_element = Value::Tuple(vec![
+            ("a".to_string(), Value::U32(1)),
+            (
+                "x".to_string(),
+                Value::Array(
+                    vec![Value::FixedArray(
+                        vec![Value::Bool(true), Value::U32(0)],
+                        Type::Bool,
+                    )],
+                    Type::FixedArray(Box::new(Type::Bool), 2),
+                ),
+            ),
+        ]);
+        assert_eq!(
+            bad_element.type_check(&ty, "inputs[0]").unwrap_err(),
+            AbiError::TypeMismatch {
+                path: "inputs[0].x[0][1]".to_string(),
+                expected: Type::Bool,
+                found: Type::U32
+            }
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            Value::decode_from_slice(&[1, 2], &[Type::U32, Type::Address]).unwrap_err(),
+            AbiError::UnexpectedEof {
+                ty: Type::Address,
+                offset: 1
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice(&[7, 3, 97], &[Type::U32, Type::String]).unwrap_err(),
+            AbiError::UnexpectedEof {
+                ty: Type::String,
+                offset: 1
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice(&[7, 2, 0xc3, 0x28], &[Type::U32, Type::String]).unwrap_err(),
+            AbiError::InvalidUtf8 { offset: 1 }
+        );
+    }
+
+    #[test]
+    fn field_elements() {
+        assert_eq!(
+            Value::field(FIELD_ORDER - 1).unwrap(),
+            Value::Field(FIELD_ORDER - 1)
+        );
+        assert_eq!(
+            Value::field(FIELD_ORDER).unwrap_err(),
+            AbiError::InvalidField(FIELD_ORDER)
+        );
+        assert_eq!(
+            Value::fields(vec![1, u64::MAX]).unwrap_err(),
+            AbiError::InvalidField(u64::MAX)
+        );
+        assert_eq!(
+            Value::fields(vec![1, 2]).unwrap(),
+            Value::Fields(vec![1, 2])
+        );
+
+        assert_eq!(Value::from(GoldilocksField(7)), Value::Field(7));
+        assert_eq!(
+            Value::from_field_elements(vec![GoldilocksField(1), GoldilocksField(FIELD_ORDER + 2)]),
+            Value::Fields(vec![1, 2])
+        );
+        assert_eq!(Value::Field(7).as_field_element(), Some(GoldilocksField(7)));
+        assert_eq!(Value::Field(FIELD_ORDER).as_field_element(), None);
+        assert_eq!(Value::U32(7).as_field_element(), None);
+
+        let num = FixedArray8([0, 0, 0, 0, 0, 0, 1, 0xffffffff]);
+        assert_eq!(
+            num.to_field_elements(),
+            [0, 0, 0, 0, 0, 0, 1, 0xffffffff].map(GoldilocksField)
+        );
+        assert_eq!(
+            Value::encode_field_elements(&[Value::String("ab".to_string()), Value::U256(num)])
+                .unwrap(),
+            [2, 97, 98, 0, 0, 0, 0, 0, 0, 1, 0xffffffff].map(GoldilocksField)
+        );
+        assert_eq!(
+            Value::encode_field_elements(&[Value::U32(1), Value::Field(FIELD_ORDER)]).unwrap_err(),
+            AbiError::InvalidField(FIELD_ORDER)
+        );
+    }
+
+    #[test]
+    fn decode_strict() {
+        let strict = DecodeOptions::strict();
+        let lenient = DecodeOptions::default();
+
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[2], &[Type::Bool], &lenient).unwrap(),
+            vec![Value::Bool(false)]
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[7, 2], &[Type::U32, Type::Bool], &strict)
+                .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::Bool,
+                offset: 1,
+                word: 2
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[1 << 32], &[Type::U32], &strict).unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::U32,
+                offset: 0,
+                word: 1 << 32
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[FIELD_ORDER], &[Type::Field], &strict)
+                .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::Field,
+                offset: 0,
+                word: FIELD_ORDER
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(
+                &[3, 7, FIELD_ORDER - 1, FIELD_ORDER],
+                &[Type::Array(Box::new(Type::Field))],
+                &strict
+            )
+            .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::Field,
+                offset: 3,
+                word: FIELD_ORDER
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[2, 97, 0x161], &[Type::String], &strict)
+                .unwrap_err(),
+            AbiError::NonCanonical {
+                ty: Type::String,
+                offset: 2,
+                word: 0x161
+            }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[1, 0, 5], &[Type::Bool, Type::Bool], &lenient)
+                .unwrap(),
+            vec![Value::Bool(true), Value::Bool(false)]
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(&[1, 0, 5], &[Type::Bool, Type::Bool], &strict)
+                .unwrap_err(),
+            AbiError::TrailingData { offset: 2 }
+        );
+        assert_eq!(
+            Value::decode_from_slice_with_options(
+                &[1, 1, 97, FIELD_ORDER - 1],
+                &[Type::Bool, Type::String, Type::Field],
+                &strict
+            )
+            .unwrap(),
+            vec![
+                Value::Bool(true),
+                Value::String("a".to_string()),
+                Value::Field(FIELD_ORDER - 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_topic() {
+        assert_eq!(
+            Value::U32(10).encode_topic().unwrap(),
+            FixedArray4([0, 0, 0, 10])
+        );
+        assert_eq!(
+            Value::Bool(true).encode_topic().unwrap(),
+            FixedArray4([0, 0, 0, 1])
+        );
+        assert_eq!(
+            Value::I32(-1).encode_topic().unwrap(),
+            FixedArray4([0, 0, 0, 0xffffffff])
+        );
+        assert_eq!(
+            Value::I64(-2).encode_topic().unwrap(),
+            FixedArray4([0, 0, 0xffffffff, 0xfffffffe])
+        );
+        assert_eq!(
+            Value::Address(FixedArray4([1, 2, 3, 4]))
+                .encode_topic()
+                .unwrap(),
+            FixedArray4([1, 2, 3, 4])
+        );
+        assert_eq!(
+            Value::String("world".to_string()).encode_topic().unwrap(),
+            FixedArray4([
+                1298737262017568572,
+                12445360621592034485,
+                13004999764278192581,
+                3441866816748036873
+            ])
+        );
+
+        assert_eq!(
+            Value::U256(FixedArray8::from(1_000_000u64))
+                .encode_topic()
+                .unwrap(),
+            FixedArray4([
+                11527978148837848074,
+                4289531113394413132,
+                6293787391853044962,
+                1944663377060687444
+            ])
+        );
+
+        let array = Value::Array(vec![Value::U32(1), Value::U32(2), Value::U32(3)], Type::U32);
+        assert_eq!(
+            array.encode_topic().unwrap(),
+            FixedArray4([
+                16280171138638196442,
+                12304378830758561978,
+                10550751364702624917,
+                2046214134348436624
+            ])
+        );
+        // the length word is not part of the hash.
+        assert_eq!(
+            Value::FixedArray(vec![Value::U32(1), Value::U32(2), Value::U32(3)], Type::U32)
+                .encode_topic()
+                .unwrap(),
+            array.encode_topic().unwrap()
+        );
+        assert_eq!(
+            Value::Fields(vec![1, 2, 3]).encode_topic().unwrap(),
+            array.encode_topic().unwrap()
+        );
+
+        assert_eq!(
+            Value::Tuple(vec![
+                ("id".to_string(), Value::U32(7)),
+                ("name".to_string(), Value::String("ola".to_string()))
+            ])
+            .encode_topic()
+            .unwrap(),
+            FixedArray4([
+                1151567286792565911,
+                4758353786661641062,
+                9144596007568226978,
+                15322320578923640908
+            ])
+        );
+        assert_eq!(
+            Value::FixedBytes(b"world".to_vec()).encode_topic().unwrap(),
+            Value::String("world".to_string()).encode_topic().unwrap()
+        );
+    }
+
     #[test]
     fn encode_u32() {
         let value = Value::U32(12);
 
         let expected_bytes = vec![12];
 
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
+    }
+
+    #[test]
+    fn encode_int() {
+        let values = vec![
+            Value::I32(-1),
+            Value::I32(i32::MAX),
+            Value::I64(i64::MIN),
+            Value::I64(-2),
+        ];
+
+        let expected_bytes = vec![
+            0xffffffff, 0x7fffffff, 0x80000000, 0, 0xffffffff, 0xfffffffe,
+        ];
+
+        assert_eq!(Value::encode(&values).unwrap(), expected_bytes);
+        assert_eq!(
+            Value::decode_from_slice(
+                &expected_bytes,
+                &[Type::I32, Type::I32, Type::I64, Type::I64]
+            )
+            .unwrap(),
+            values
+        );
+
+        // signed values share the layout of the unsigned ones of the same bits.
+        assert_eq!(
+            Value::encode(&values).unwrap(),
+            Value::encode(&[
+                Value::U32(u32::MAX as u64),
+                Value::U32(i32::MAX as u64),
+                Value::U64(i64::MIN as u64),
+                Value::U64(-2i64 as u64),
+            ])
+            .unwrap()
+        );
     }
 
     #[test]
@@ -702,7 +2339,7 @@ mod test {
         let value = Value::U256(FixedArray8(u256));
 
         let expected_bytes = vec![1, 2, 3, 4, 5, 6, 7, 8];
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
         let expected_hex = "0x0000000100000002000000030000000400000005000000060000000700000008";
         assert_eq!(FixedArray8(u256).to_hex_string(), expected_hex);
     }
@@ -713,7 +2350,7 @@ mod test {
         let value = Value::Address(FixedArray4(addr));
 
         let expected_bytes = vec![1, 2, 3, 4];
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
 
         let expected_hex = "0x0000000000000001000000000000000200000000000000030000000000000004";
         assert_eq!(FixedArray4(addr).to_hex_string(), expected_hex);
@@ -726,7 +2363,7 @@ mod test {
 
         let expected_bytes = vec![1, 2, 3, 4];
 
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
     }
 
     #[test]
@@ -735,8 +2372,8 @@ mod test {
 
         let false_vec = vec![0];
 
-        assert_eq!(Value::encode(&[Value::Bool(true)]), true_vec);
-        assert_eq!(Value::encode(&[Value::Bool(false)]), false_vec);
+        assert_eq!(Value::encode(&[Value::Bool(true)]).unwrap(), true_vec);
+        assert_eq!(Value::encode(&[Value::Bool(false)]).unwrap(), false_vec);
     }
 
     #[test]
@@ -748,7 +2385,7 @@ mod test {
 
         let expected_bytes = [57, 108];
 
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
     }
 
     #[test]
@@ -757,7 +2394,7 @@ mod test {
 
         let expected_bytes = [5, 111, 108, 97, 118, 109];
         assert_eq!(
-            Value::encode(&[Value::String("olavm".to_string())]),
+            Value::encode(&[Value::String("olavm".to_string())]).unwrap(),
             expected_bytes
         );
     }
@@ -777,7 +2414,7 @@ mod test {
 
         let expected_bytes = [2, 1, 2, 3, 4, 5, 6, 7, 8];
 
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
     }
 
     #[test]
@@ -791,7 +2428,7 @@ mod test {
 
         let expected_bytes = [1, 2, 3, 4, 99];
 
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
     }
 
     #[test]
@@ -805,7 +2442,7 @@ mod test {
 
         let expected_bytes = [5, 111, 108, 97, 118, 109, 99];
 
-        assert_eq!(Value::encode(&[value]), expected_bytes);
+        assert_eq!(Value::encode(&[value]).unwrap(), expected_bytes);
     }
 
     #[test]
@@ -823,6 +2460,75 @@ mod test {
         ];
 
         let expected = [5, 111, 108, 97, 118, 109, 99, 2, 1, 2, 1, 3];
-        assert_eq!(Value::encode(&values), expected);
+        assert_eq!(Value::encode(&values).unwrap(), expected);
+    }
+
+    #[test]
+    fn display_value() {
+        let color = Type::Enum {
+            name: "Color".to_string(),
+            variants: vec!["Red".to_string()],
+        };
+        let ty = Type::Tuple(vec![
+            ("".to_string(), Type::U256),
+            ("".to_string(), Type::Address),
+            ("".to_string(), Type::FixedBytes(2)),
+            ("".to_string(), Type::String),
+            ("".to_string(), color.clone()),
+            ("".to_string(), Type::Fields),
+            ("".to_string(), Type::FixedArray(Box::new(Type::I32), 2)),
+        ]);
+        let value = Value::Tuple(vec![
+            (
+                "".to_string(),
+                Value::U256(FixedArray8([0, 0, 0, 1, 0, 0, 0, 0])),
+            ),
+            ("".to_string(), Value::Address(FixedArray4([0, 0, 0, 0xab]))),
+            ("".to_string(), Value::FixedBytes(vec![0x0a, 0xff])),
+            ("".to_string(), Value::String("say \"hi\"\n".to_string())),
+            ("".to_string(), Value::Enum("Red".to_string(), color)),
+            ("".to_string(), Value::Fields(vec![1, 2])),
+            (
+                "".to_string(),
+                Value::FixedArray(vec![Value::I32(-1), Value::I32(5)], Type::I32),
+            ),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            format!(
+                "(340282366920938463463374607431768211456, 0x{:064x}, 0x0aff, \"say \\\"hi\\\"\\n\", Red, [1, 2], [-1, 5])",
+                0xab
+            )
+        );
+        assert_eq!(Value::parse(&value.to_string(), &ty).unwrap(), value);
+    }
+
+    #[test]
+    fn display_value_pretty() {
+        let book = |id: u64, name: &str| {
+            Value::Tuple(vec![
+                ("id".to_string(), Value::U32(id)),
+                ("name".to_string(), Value::String(name.to_string())),
+            ])
+        };
+        let value = Value::Tuple(vec![
+            ("owner".to_string(), Value::Address(FixedArray4([0; 4]))),
+            (
+                "books".to_string(),
+                Value::Array(vec![book(1, "a"), book(2, "b")], Type::Tuple(vec![])),
+            ),
+            ("tags".to_string(), Value::Array(vec![], Type::String)),
+        ]);
+
+        assert_eq!(
+            format!("{:#}", value),
+            format!(
+                "(\n    owner: 0x{:064x},\n    books: [\n        (id: 1, name: \"a\"),\n        (id: 2, name: \"b\"),\n    ],\n    tags: [],\n)",
+                0
+            )
+        );
+        assert_eq!(format!("{:#}", book(1, "a")), "(id: 1, name: \"a\")");
+        assert_eq!(format!("{:#}", Value::U32(1)), "1");
     }
 }